@@ -123,7 +123,7 @@ fn list_index(c: &mut Criterion) {
         b.iter(|| {
             let v: Vec<&Person> = v
                 .iter()
-                .filter(|p| p.0 == FIND_ID && &p.1 == &FIND_PERSON.1)
+                .filter(|p| p.0 == FIND_ID && p.1 == FIND_PERSON.1)
                 .collect();
             assert_eq!(&FIND_PERSON, v[0]);
         })
@@ -1,19 +1,32 @@
 //! Different kinds of collections which are using `Indices`.
 //!
-//! This collections only support one Index for one property.
+//! This collections support one Index for one property, or many Indices with a tuple of `Stores`
+//! and a tuple of functions for the `Keys` (see: [`crate::index::store::Indexer`]).
+//! Every `Store` has its own [`Retriever`] (`idx_by`).
 //!
+pub mod order;
+pub mod page;
 pub mod ro;
 pub mod rw;
 
-use crate::index::{
-    indices::Indices,
-    store::{Filterable, MetaData, View, ViewCreator},
-    Filter, Indexable,
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    collections::{
+        order::OrderBy,
+        page::{after, slices_page, Cursor, KeyCursor, Page},
+    },
+    index::{
+        indices::Indices,
+        store::{Filterable, MetaData, Ordered, View, ViewCreator},
+        Filter, Indexable,
+    },
 };
 
 /// A `Retriever` is the main interface for get Items by an given query.
 #[repr(transparent)]
-pub struct Retriever<'a, F, I>(Filter<'a, F, I>);
+pub struct Retriever<'a, F, I>(pub(crate) Filter<'a, F, I>);
 
 impl<'a, F, I> Retriever<'a, F, I>
 where
@@ -131,6 +144,41 @@ where
         self.0.filter.get_many(keys).items(self.0.items)
     }
 
+    /// Like `get_many`, but the lookups of the `keys` are split across threads (with [`rayon`]).
+    /// The order of the `Items` is the same as by `get_many`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use fast_forward::{index::MultiUIntIndex, collections::ro::IList};
+    /// use rayon::prelude::*;
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|i| i % 10, (0..100).collect::<Vec<usize>>());
+    ///
+    /// let sum: usize = l.idx().par_get_many([2, 5]).sum();
+    /// assert_eq!(470 + 500, sum);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_get_many<II>(
+        &self,
+        keys: II,
+    ) -> impl ParallelIterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        II: IntoIterator<Item = F::Key>,
+        F: Sync,
+        F::Key: Send,
+        F::Index: Sync,
+        I: Indexable<F::Index> + Sync,
+        <I as Indexable<F::Index>>::Output: Sync,
+    {
+        let (filter, items) = (self.0.filter, self.0.items);
+        keys.into_iter()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .flat_map_iter(move |key| filter.get(&key).iter())
+            .map(move |idx| items.item(idx))
+    }
+
     /// Return filter methods from the `Store`.
     ///
     /// # Example
@@ -168,6 +216,359 @@ where
         predicate(&self.0).items(self.0.items)
     }
 
+    /// The same as `filter`, but returns a parallel iterator (with [`rayon`]) over the `Items`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MultiUIntIndex, collections::ro::IList};
+    /// use rayon::prelude::*;
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|i| i % 10, (0..100).collect::<Vec<usize>>());
+    ///
+    /// let r = l.idx().par_filter(|f| f.eq(&2) | f.eq(&5)).map(|i| i * 2).collect::<Vec<_>>();
+    /// assert_eq!(vec![4, 10, 24, 30], r[..4]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_filter<P>(
+        &self,
+        predicate: P,
+    ) -> impl IndexedParallelIterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        P: Fn(&Filter<'a, F, I>) -> Indices<'a, F::Index>,
+        F::Index: Clone + Send,
+        I: Indexable<F::Index> + Sync,
+        <I as Indexable<F::Index>>::Output: Sync,
+    {
+        predicate(&self.0).par_items(self.0.items)
+    }
+
+    /// Combined an `Index` based selection (`select`) with a `residual` predicate on the `Items`.
+    /// The `residual` predicate is only evaluated for the candidates of the selection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiUIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = vec![Car(2, "BMW".into()), Car(5, "Audi".into()), Car(2, "VW".into())];
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|c| c.0, cars);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(2, "VW".into())],
+    ///     l.idx()
+    ///      .filter_with(|fltr| fltr.eq(&2), |c| c.1.starts_with('V'))
+    ///      .collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[inline]
+    pub fn filter_with<S, P>(
+        &self,
+        select: S,
+        residual: P,
+    ) -> impl Iterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        S: Fn(&Filter<'a, F, I>) -> Indices<'a, F::Index>,
+        P: Fn(&<I as Indexable<F::Index>>::Output) -> bool,
+        I: Indexable<F::Index>,
+        F::Index: Clone,
+    {
+        select(&self.0)
+            .items(self.0.items)
+            .filter(move |item| residual(item))
+    }
+
+    /// Return the `Items` for the given filter, sorted by [`OrderBy`] (see: [`crate::collections::order`]).
+    #[inline]
+    pub fn filter_sorted<P>(
+        &self,
+        predicate: P,
+        order: &OrderBy<'_>,
+    ) -> impl Iterator<Item = &'a <I as Indexable<usize>>::Output>
+    where
+        P: Fn(&Filter<'a, F, I>) -> Indices<'a, usize>,
+        F: Filterable<Index = usize>,
+        I: Indexable<usize>,
+    {
+        let items = self.0.items;
+        order
+            .sort(&predicate(&self.0))
+            .into_iter()
+            .map(move |pos| items.item(&pos))
+    }
+
+    /// Return the `k` `Items` with the largest `Keys` of this ordered `Index` (descending),
+    /// which are contained in the given `candidates` (e.g. the result of an equality selection).
+    /// The walk over the `Index` stops, if `k` `Items` (or all `candidates`) are found. Ties are ordered by the position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{MapIndex, MultiUIntIndex};
+    /// use fast_forward::collections::ro::IRefList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Order { day: usize, customer: &'static str }
+    ///
+    /// let orders = vec![
+    ///     Order { day: 3, customer: "X" },
+    ///     Order { day: 7, customer: "Y" },
+    ///     Order { day: 5, customer: "X" },
+    ///     Order { day: 1, customer: "X" },
+    /// ];
+    ///
+    /// let days = IRefList::<MultiUIntIndex, _>::new(|o| o.day, &orders);
+    /// let customers = IRefList::<MapIndex, _>::new(|o| o.customer.to_string(), &orders);
+    ///
+    /// // two most recent orders for customer X
+    /// let x = customers.idx().eq(&"X".into());
+    /// assert_eq!(
+    ///     vec![5, 3],
+    ///     days.idx().top_k(2, x).map(|o| o.day).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn top_k(
+        &self,
+        k: usize,
+        candidates: Indices<'a>,
+    ) -> impl Iterator<Item = &'a <I as Indexable<usize>>::Output>
+    where
+        F: Ordered<Index = usize>,
+        I: Indexable<usize>,
+    {
+        // stop the walk, if all candidates are found
+        let k = k.min(candidates.as_slice().len());
+        let positions = self
+            .0
+            .filter
+            .range(..)
+            .rev()
+            .flatten()
+            .filter(move |pos| candidates.as_slice().binary_search(pos).is_ok())
+            .take(k);
+        self.0.items.items(positions)
+    }
+
+    /// Return the `k` `Items` with the smallest `Keys` of this ordered `Index` (ascending),
+    /// which are contained in the given `candidates` (see: [`Retriever::top_k`]).
+    pub fn bottom_k(
+        &self,
+        k: usize,
+        candidates: Indices<'a>,
+    ) -> impl Iterator<Item = &'a <I as Indexable<usize>>::Output>
+    where
+        F: Ordered<Index = usize>,
+        I: Indexable<usize>,
+    {
+        // stop the walk, if all candidates are found
+        let k = k.min(candidates.as_slice().len());
+        let positions = self
+            .0
+            .filter
+            .range(..)
+            .flatten()
+            .filter(move |pos| candidates.as_slice().binary_search(pos).is_ok())
+            .take(k);
+        self.0.items.items(positions)
+    }
+
+    /// Return the `Items` for the given filter, skip the first `offset` and take maximal `limit` `Items`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiUIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = vec![Car(2, "BMW".into()), Car(5, "Audi".into()), Car(2, "VW".into())];
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|c| c.0, cars);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(2, "VW".into())],
+    ///     l.idx().filter_page(|f| f.eq(&2), 1, 10).collect::<Vec<_>>()
+    /// );
+    /// assert_eq!(
+    ///     vec![&Car(2, "VW".into()), &Car(5, "Audi".into())],
+    ///     l.idx().get_many_page([2, 5], 1, 10).collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[inline]
+    pub fn filter_page<P>(
+        &self,
+        predicate: P,
+        offset: usize,
+        limit: usize,
+    ) -> impl Iterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        P: Fn(&Filter<'a, F, I>) -> Indices<'a, F::Index>,
+        I: Indexable<F::Index>,
+        F::Index: Clone,
+    {
+        predicate(&self.0).page(offset, limit).items(self.0.items)
+    }
+
+    /// Like `get_many`, but skip the first `offset` and take maximal `limit` `Items`.
+    #[inline]
+    pub fn get_many_page<II>(
+        &self,
+        keys: II,
+        offset: usize,
+        limit: usize,
+    ) -> impl Iterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        II: IntoIterator<Item = F::Key> + 'a,
+        I: Indexable<F::Index>,
+    {
+        let filter = self.0.filter;
+        let slices = keys.into_iter().map(move |k| filter.get(&k));
+        self.0.items.items(slices_page(slices, offset, limit))
+    }
+
+    /// Return a [`Page`] with maximal `limit` `Items` for the given filter (in position order),
+    /// which starts after the given [`Cursor`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiUIntIndex};
+    /// use fast_forward::collections::rw::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let mut l = IList::<MultiUIntIndex, _, _>::from_vec(|c| c.0, vec![
+    ///     Car(2, "BMW".into()),
+    ///     Car(5, "Audi".into()),
+    ///     Car(2, "VW".into()),
+    /// ]);
+    ///
+    /// let page = l.idx().filter_after(|f| f.eq(&2), None, 1);
+    /// assert_eq!(&[&Car(2, "BMW".into())], page.items());
+    /// let cursor = page.cursor();
+    ///
+    /// // the cursor remains valid after a push
+    /// l.push(Car(2, "Opel".into()));
+    ///
+    /// let page = l.idx().filter_after(|f| f.eq(&2), cursor, 5);
+    /// assert_eq!(&[&Car(2, "VW".into()), &Car(2, "Opel".into())], page.items());
+    /// ```
+    pub fn filter_after<P>(
+        &self,
+        predicate: P,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Page<'a, <I as Indexable<usize>>::Output>
+    where
+        P: Fn(&Filter<'a, F, I>) -> Indices<'a, usize>,
+        F: Filterable<Index = usize>,
+        I: Indexable<usize>,
+    {
+        let idxs = predicate(&self.0);
+        Page::new(
+            self.0.items,
+            after(idxs.as_slice(), cursor).iter().take(limit).copied(),
+        )
+    }
+
+    /// Return a [`Page`] with maximal `limit` `Items` ordered by the `Key` of this `Index`
+    /// (ties by position), which starts after the given [`KeyCursor`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(i32, String);
+    ///
+    /// let l = IList::<MultiIntIndex, _>::new(|c| c.0, vec![
+    ///     Car(3, "BMW".into()),
+    ///     Car(-1, "Audi".into()),
+    ///     Car(3, "VW".into()),
+    /// ]);
+    ///
+    /// let page = l.idx().page_by_key(None, 2);
+    /// assert_eq!(&[&Car(-1, "Audi".into()), &Car(3, "BMW".into())], page.items());
+    ///
+    /// let page = l.idx().page_by_key(page.key_cursor(|c| c.0).as_ref(), 2);
+    /// assert_eq!(&[&Car(3, "VW".into())], page.items());
+    /// ```
+    pub fn page_by_key(
+        &self,
+        cursor: Option<&KeyCursor<F::Key>>,
+        limit: usize,
+    ) -> Page<'a, <I as Indexable<usize>>::Output>
+    where
+        F: Ordered<Index = usize>,
+        I: Indexable<usize>,
+    {
+        page::by_key(self.0.filter, self.0.items, cursor, limit)
+    }
+
+    /// Returns the number of `Items` for the given `Key`, without accessing the `Items`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiUIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = vec![Car(2, "BMW".into()), Car(5, "Audi".into()), Car(2, "VW".into())];
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|c| c.0, cars);
+    ///
+    /// assert_eq!(2, l.idx().count(&2));
+    /// assert_eq!(3, l.idx().count_many([2, 5, 99]));
+    /// assert_eq!(1, l.idx().count_filter(|f| f.eq(&5) & f.eq(&5)));
+    /// assert!(l.idx().exists_any([99, 5]));
+    /// assert!(!l.idx().exists_any([99, 100]));
+    /// ```
+    #[inline]
+    pub fn count(&self, key: &F::Key) -> usize {
+        self.0.filter.get(key).len()
+    }
+
+    /// Returns the number of `Items` for all given `Keys` (like: `get_many(keys).count()`).
+    #[inline]
+    pub fn count_many<II>(&self, keys: II) -> usize
+    where
+        II: IntoIterator<Item = F::Key>,
+    {
+        keys.into_iter().map(|k| self.0.filter.get(&k).len()).sum()
+    }
+
+    /// Returns the number of `Items` for the given filter (like: `filter(predicate).count()`).
+    #[inline]
+    pub fn count_filter<P>(&self, predicate: P) -> usize
+    where
+        P: Fn(&Filter<'a, F, I>) -> Indices<'a, F::Index>,
+        F::Index: Clone,
+    {
+        predicate(&self.0).as_slice().len()
+    }
+
+    /// Checks whether one of the given `Keys` exists.
+    #[inline]
+    pub fn exists_any<II>(&self, keys: II) -> bool
+    where
+        II: IntoIterator<Item = F::Key>,
+    {
+        keys.into_iter().any(|k| self.0.filter.contains(&k))
+    }
+
     /// Create a `View` by a given list of keys.
     /// The view represents a subset of the items in the list.
     /// This is particularly useful if I don't want to show all items for non-existing rights.
@@ -260,7 +661,7 @@ impl<'a, F: Filterable, I> Viewer<'a, F, I> {
     pub fn filter<P>(
         &'a self,
         predicate: P,
-    ) -> impl Iterator<Item = &'_ <I as Indexable<F::Index>>::Output>
+    ) -> impl Iterator<Item = &'a <I as Indexable<F::Index>>::Output>
     where
         P: Fn(&Filter<'a, View<F>, I>) -> Indices<'a, F::Index>,
         I: Indexable<F::Index>,
@@ -268,4 +669,224 @@ impl<'a, F: Filterable, I> Viewer<'a, F, I> {
     {
         predicate(&Filter::new(&self.view, self.items)).items(self.items)
     }
+
+    /// Combined an `Index` based selection (`select`) with a `residual` predicate on the `Items`.
+    /// The `residual` predicate is only evaluated for the candidates of the selection.
+    #[inline]
+    pub fn filter_with<S, P>(
+        &'a self,
+        select: S,
+        residual: P,
+    ) -> impl Iterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        S: Fn(&Filter<'a, View<F>, I>) -> Indices<'a, F::Index>,
+        P: Fn(&<I as Indexable<F::Index>>::Output) -> bool,
+        I: Indexable<F::Index>,
+        F::Index: Clone + 'a,
+    {
+        select(&Filter::new(&self.view, self.items))
+            .items(self.items)
+            .filter(move |item| residual(item))
+    }
+
+    /// Returns the number of `Items` of this `View` for the given `Key`, without accessing the `Items`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiUIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let cars = vec![Car(2, "BMW".into()), Car(5, "Audi".into()), Car(2, "VW".into())];
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|c| c.0, cars);
+    /// let view = l.idx().create_view([2]);
+    ///
+    /// assert_eq!(2, view.count(&2));
+    /// assert_eq!(0, view.count(&5));
+    /// assert_eq!(2, view.count_many([2, 5, 99]));
+    /// assert_eq!(2, view.count_filter(|f| f.eq(&2) | f.eq(&5)));
+    /// assert!(view.exists_any([99, 2]));
+    /// assert!(!view.exists_any([99, 5]));
+    /// ```
+    #[inline]
+    pub fn count(&self, key: &F::Key) -> usize {
+        self.view.get(key).len()
+    }
+
+    /// Returns the number of `Items` of this `View` for all given `Keys` (like: `get_many(keys).count()`).
+    #[inline]
+    pub fn count_many<II>(&self, keys: II) -> usize
+    where
+        II: IntoIterator<Item = F::Key>,
+    {
+        keys.into_iter().map(|k| self.view.get(&k).len()).sum()
+    }
+
+    /// Returns the number of `Items` of this `View` for the given filter (like: `filter(predicate).count()`).
+    #[inline]
+    pub fn count_filter<P>(&'a self, predicate: P) -> usize
+    where
+        P: Fn(&Filter<'a, View<F>, I>) -> Indices<'a, F::Index>,
+        F::Index: Clone + 'a,
+    {
+        predicate(&Filter::new(&self.view, self.items))
+            .as_slice()
+            .len()
+    }
+
+    /// Checks whether one of the given `Keys` exists in this `View`.
+    #[inline]
+    pub fn exists_any<II>(&self, keys: II) -> bool
+    where
+        II: IntoIterator<Item = F::Key>,
+    {
+        keys.into_iter().any(|k| self.view.contains(&k))
+    }
+
+    /// Return the `Items` of this `View` for the given filter, skip the first `offset` and take maximal `limit` `Items`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiUIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|c| c.0, vec![
+    ///     Car(2, "BMW".into()),
+    ///     Car(5, "Audi".into()),
+    ///     Car(2, "VW".into()),
+    ///     Car(7, "Opel".into()),
+    /// ]);
+    /// let view = l.idx().create_view([2, 5]);
+    ///
+    /// assert_eq!(
+    ///     vec![&Car(2, "VW".into())],
+    ///     view.filter_page(|f| f.eq(&2), 1, 10).collect::<Vec<_>>()
+    /// );
+    /// assert_eq!(
+    ///     vec![&Car(2, "VW".into()), &Car(5, "Audi".into())],
+    ///     view.get_many_page([2, 5, 7], 1, 10).collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[inline]
+    pub fn filter_page<P>(
+        &'a self,
+        predicate: P,
+        offset: usize,
+        limit: usize,
+    ) -> impl Iterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        P: Fn(&Filter<'a, View<F>, I>) -> Indices<'a, F::Index>,
+        I: Indexable<F::Index>,
+        F::Index: Clone + 'a,
+    {
+        predicate(&Filter::new(&self.view, self.items))
+            .page(offset, limit)
+            .items(self.items)
+    }
+
+    /// Like `get_many`, but skip the first `offset` and take maximal `limit` `Items`.
+    #[inline]
+    pub fn get_many_page<II>(
+        &'a self,
+        keys: II,
+        offset: usize,
+        limit: usize,
+    ) -> impl Iterator<Item = &'a <I as Indexable<F::Index>>::Output>
+    where
+        II: IntoIterator<Item = F::Key> + 'a,
+        I: Indexable<F::Index>,
+    {
+        let view = &self.view;
+        let slices = keys.into_iter().map(move |k| view.get(&k));
+        self.items.items(slices_page(slices, offset, limit))
+    }
+
+    /// Return a [`Page`] with maximal `limit` `Items` of this `View` for the given filter (in position order),
+    /// which starts after the given [`Cursor`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiUIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let l = IList::<MultiUIntIndex, _>::new(|c| c.0, vec![
+    ///     Car(2, "BMW".into()),
+    ///     Car(5, "Audi".into()),
+    ///     Car(2, "VW".into()),
+    /// ]);
+    /// let view = l.idx().create_view([2]);
+    ///
+    /// let page = view.filter_after(|f| f.eq(&2), None, 1);
+    /// assert_eq!(&[&Car(2, "BMW".into())], page.items());
+    ///
+    /// let page = view.filter_after(|f| f.eq(&2) | f.eq(&5), page.cursor(), 5);
+    /// assert_eq!(&[&Car(2, "VW".into())], page.items());
+    /// ```
+    pub fn filter_after<P>(
+        &'a self,
+        predicate: P,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Page<'a, <I as Indexable<usize>>::Output>
+    where
+        P: Fn(&Filter<'a, View<F>, I>) -> Indices<'a, usize>,
+        F: Filterable<Index = usize>,
+        I: Indexable<usize>,
+    {
+        let idxs = predicate(&Filter::new(&self.view, self.items));
+        Page::new(
+            self.items,
+            after(idxs.as_slice(), cursor).iter().take(limit).copied(),
+        )
+    }
+
+    /// Return a [`Page`] with maximal `limit` `Items` of this `View` ordered by the `Key`
+    /// (ties by position), which starts after the given [`KeyCursor`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::{store::Store, MultiIntIndex};
+    /// use fast_forward::collections::ro::IList;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(i32, String);
+    ///
+    /// let l = IList::<MultiIntIndex, _>::new(|c| c.0, vec![
+    ///     Car(3, "BMW".into()),
+    ///     Car(-1, "Audi".into()),
+    ///     Car(3, "VW".into()),
+    ///     Car(7, "Opel".into()),
+    /// ]);
+    /// let view = l.idx().create_view([3, 7]);
+    ///
+    /// let page = view.page_by_key(None, 2);
+    /// assert_eq!(&[&Car(3, "BMW".into()), &Car(3, "VW".into())], page.items());
+    ///
+    /// let page = view.page_by_key(page.key_cursor(|c| c.0).as_ref(), 2);
+    /// assert_eq!(&[&Car(7, "Opel".into())], page.items());
+    /// ```
+    pub fn page_by_key(
+        &self,
+        cursor: Option<&KeyCursor<F::Key>>,
+        limit: usize,
+    ) -> Page<'a, <I as Indexable<usize>>::Output>
+    where
+        F: Ordered<Index = usize>,
+        I: Indexable<usize>,
+    {
+        page::by_key(&self.view, self.items, cursor, limit)
+    }
 }
@@ -0,0 +1,256 @@
+//! Ordering the results of a [`Retriever`] by one or many ordered `Indices`
+//! (see: [`crate::index::store::Ordered`]).
+//!
+//! The positions of the result are not sorted by comparing the `Items`.
+//! Every ordered `Index` is walked in the `Key` order and gives every position a rank.
+//! The result is sorted by the ranks, ties are ordered by the position.
+//!
+use crate::{
+    collections::Retriever,
+    index::{indices::Indices, store::Ordered},
+};
+
+/// The direction of an ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+/// Create the rank of the positions for an ordered `Index`.
+trait Rank {
+    /// Returns for every position (must be sorted) the rank.
+    /// Positions which are not found in the `Index`, get the rank: `usize::MAX`.
+    fn ranks(&self, positions: &[usize], direction: Direction) -> Vec<usize>;
+}
+
+struct OrderedRank<'a, F>(&'a F);
+
+impl<F> Rank for OrderedRank<'_, F>
+where
+    F: Ordered<Index = usize>,
+{
+    fn ranks(&self, positions: &[usize], direction: Direction) -> Vec<usize> {
+        let mut ranks = vec![usize::MAX; positions.len()];
+        let mut found = 0;
+
+        let groups: Box<dyn Iterator<Item = &[usize]>> = match direction {
+            Direction::Asc => Box::new(self.0.range(..)),
+            Direction::Desc => Box::new(self.0.range(..).rev()),
+        };
+
+        for (rank, group) in groups.enumerate() {
+            for pos in group {
+                if let Ok(i) = positions.binary_search(pos) {
+                    ranks[i] = rank;
+                    found += 1;
+                }
+            }
+
+            if found == positions.len() {
+                break;
+            }
+        }
+
+        ranks
+    }
+}
+
+/// `OrderBy` describes, how the results are sorted: by one (or many) ordered `Indices`.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::{
+///     collections::{order::OrderBy, ro::IRefList},
+///     index::{MapIndex, MultiUIntIndex, SortedIndex},
+/// };
+///
+/// #[derive(Debug, PartialEq)]
+/// pub struct Car(usize, &'static str, u32);
+///
+/// let cars = vec![
+///     Car(1, "BMW", 30),
+///     Car(2, "VW", 10),
+///     Car(3, "BMW", 20),
+///     Car(3, "BMW", 10),
+/// ];
+///
+/// let ids = IRefList::<MultiUIntIndex, _>::new(|c| c.0, &cars);
+/// let names = IRefList::<MapIndex, _>::new(|c| c.1.to_string(), &cars);
+/// let prices = IRefList::<SortedIndex<u32>, _>::new(|c| c.2, &cars);
+///
+/// // cars with name BMW ordered by id descending and then by price
+/// let order = OrderBy::desc(ids.idx()).then_asc(prices.idx());
+/// assert_eq!(
+///     vec![&Car(3, "BMW", 10), &Car(3, "BMW", 20), &Car(1, "BMW", 30)],
+///     names.idx().filter_sorted(|f| f.eq(&"BMW".into()), &order).collect::<Vec<_>>()
+/// );
+/// ```
+pub struct OrderBy<'a> {
+    keys: Vec<(Box<dyn Rank + 'a>, Direction)>,
+}
+
+impl<'a> OrderBy<'a> {
+    /// Order by the given `Index` ascending.
+    pub fn asc<F, I>(retriever: Retriever<'a, F, I>) -> Self
+    where
+        F: Ordered<Index = usize>,
+    {
+        Self::by(retriever, Direction::Asc)
+    }
+
+    /// Order by the given `Index` descending.
+    pub fn desc<F, I>(retriever: Retriever<'a, F, I>) -> Self
+    where
+        F: Ordered<Index = usize>,
+    {
+        Self::by(retriever, Direction::Desc)
+    }
+
+    /// Order by the given `Index` and [`Direction`].
+    pub fn by<F, I>(retriever: Retriever<'a, F, I>, direction: Direction) -> Self
+    where
+        F: Ordered<Index = usize>,
+    {
+        Self { keys: Vec::new() }.then_by(retriever, direction)
+    }
+
+    /// Secondary order (for equal `Keys`) by the given `Index` ascending.
+    pub fn then_asc<F, I>(self, retriever: Retriever<'a, F, I>) -> Self
+    where
+        F: Ordered<Index = usize>,
+    {
+        self.then_by(retriever, Direction::Asc)
+    }
+
+    /// Secondary order (for equal `Keys`) by the given `Index` descending.
+    pub fn then_desc<F, I>(self, retriever: Retriever<'a, F, I>) -> Self
+    where
+        F: Ordered<Index = usize>,
+    {
+        self.then_by(retriever, Direction::Desc)
+    }
+
+    /// Secondary order (for equal `Keys`) by the given `Index` and [`Direction`].
+    pub fn then_by<F, I>(mut self, retriever: Retriever<'a, F, I>, direction: Direction) -> Self
+    where
+        F: Ordered<Index = usize>,
+    {
+        self.keys
+            .push((Box::new(OrderedRank(retriever.0.filter)), direction));
+        self
+    }
+
+    /// Sort the given `Indices` (positions).
+    pub fn sort(&self, indices: &Indices<'_>) -> Vec<usize> {
+        let positions = indices.as_slice();
+        let ranks = self
+            .keys
+            .iter()
+            .map(|(r, d)| r.ranks(positions, *d))
+            .collect::<Vec<_>>();
+
+        // the sort is stable, so ties are ordered by the position
+        let mut order = (0..positions.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| {
+            ranks
+                .iter()
+                .map(|r| r[*a].cmp(&r[*b]))
+                .find(|o| o.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        order.into_iter().map(|i| positions[i]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        collections::ro::IRefList,
+        index::{MapIndex, MultiIntIndex, SortedIndex, UniqueUIntIndex},
+    };
+    use rstest::{fixture, rstest};
+
+    #[derive(Debug, PartialEq)]
+    struct Car(usize, &'static str, i32);
+
+    #[fixture]
+    fn cars() -> Vec<Car> {
+        vec![
+            Car(1, "BMW", 30),
+            Car(2, "VW", -10),
+            Car(3, "BMW", 20),
+            Car(4, "BMW", 30),
+            Car(5, "Audi", 20),
+        ]
+    }
+
+    #[rstest]
+    fn order_by_uint(cars: Vec<Car>) {
+        let ids = IRefList::<UniqueUIntIndex, _>::new(|c| c.0, &cars);
+        let names = IRefList::<MapIndex, _>::new(|c| c.1.to_string(), &cars);
+
+        let r = names
+            .idx()
+            .filter_sorted(|f| f.eq(&"BMW".into()), &OrderBy::desc(ids.idx()))
+            .map(|c| c.0)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![4, 3, 1], r);
+
+        let r = names
+            .idx()
+            .filter_sorted(
+                |f| f.eq(&"BMW".into()) | f.eq(&"VW".into()),
+                &OrderBy::asc(ids.idx()),
+            )
+            .map(|c| c.0)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 3, 4], r);
+    }
+
+    #[rstest]
+    fn multi_keys_and_ties(cars: Vec<Car>) {
+        let ids = IRefList::<UniqueUIntIndex, _>::new(|c| c.0, &cars);
+        let prices = IRefList::<MultiIntIndex, _>::new(|c| c.2, &cars);
+        let all = Indices::from([0, 1, 2, 3, 4]);
+
+        // ties by position
+        assert_eq!(vec![1, 2, 4, 0, 3], OrderBy::asc(prices.idx()).sort(&all));
+        assert_eq!(vec![0, 3, 2, 4, 1], OrderBy::desc(prices.idx()).sort(&all));
+
+        // secondary index
+        assert_eq!(
+            vec![3, 0, 4, 2, 1],
+            OrderBy::desc(prices.idx()).then_desc(ids.idx()).sort(&all)
+        );
+        assert_eq!(
+            vec![1, 2, 4, 0, 3],
+            OrderBy::by(prices.idx(), Direction::Asc)
+                .then_by(ids.idx(), Direction::Asc)
+                .sort(&all)
+        );
+
+        assert!(OrderBy::asc(ids.idx()).sort(&Indices::empty()).is_empty());
+    }
+
+    #[rstest]
+    fn top_and_bottom_k(cars: Vec<Car>) {
+        let names = IRefList::<MapIndex, _>::new(|c| c.1.to_string(), &cars);
+        let prices = IRefList::<MultiIntIndex, _>::new(|c| c.2, &cars);
+        let sorted = IRefList::<SortedIndex<i32>, _>::new(|c| c.2, &cars);
+        let bmw = || names.idx().eq(&"BMW".into());
+
+        let ids = |it: &mut dyn Iterator<Item = &Car>| it.map(|c| c.0).collect::<Vec<_>>();
+
+        // ties by position
+        assert_eq!(vec![1, 4], ids(&mut prices.idx().top_k(2, bmw())));
+        assert_eq!(vec![3, 1], ids(&mut prices.idx().bottom_k(2, bmw())));
+        assert_eq!(vec![1, 4, 3], ids(&mut sorted.idx().top_k(10, bmw())));
+        assert_eq!(vec![3], ids(&mut sorted.idx().bottom_k(1, bmw())));
+
+        assert!(ids(&mut sorted.idx().top_k(0, bmw())).is_empty());
+        assert!(ids(&mut sorted.idx().top_k(3, Indices::empty())).is_empty());
+    }
+}
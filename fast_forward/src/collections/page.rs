@@ -0,0 +1,302 @@
+//! Pagination of the results from a [`crate::collections::Retriever`] or [`crate::collections::Viewer`].
+//!
+//! There are two kinds of pagination:
+//! - `offset` and `limit`: slices the list of positions (e.g. `Retriever::filter_page`)
+//! - keyset: a [`Cursor`] (last position) or a [`KeyCursor`] (last `Key` and position),
+//!   the next page starts after the cursor (e.g. `Retriever::filter_after`)
+//!
+//! A keyset cursor remains valid, if new `Items` are pushed to a [`crate::collections::rw::IList`],
+//! because a push appends the new `Item` at the end (the existing positions are not changed).
+//!
+use std::ops::Bound;
+
+use crate::index::{store::Ordered, Indexable};
+
+/// Keyset cursor for results in position order: the next page starts after this position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor(pub usize);
+
+/// Keyset cursor for results in `Key` order: the next page starts after this `Key` and position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCursor<K> {
+    pub key: K,
+    pub position: usize,
+}
+
+/// One page of `Items` from a keyset pagination.
+#[derive(Debug)]
+pub struct Page<'a, T: ?Sized> {
+    items: Vec<&'a T>,
+    last: Option<usize>,
+}
+
+impl<'a, T: ?Sized> Page<'a, T> {
+    pub(crate) fn new<I, P>(items: &'a I, positions: P) -> Self
+    where
+        I: Indexable<usize, Output = T> + ?Sized,
+        P: IntoIterator<Item = usize>,
+    {
+        let mut last = None;
+        let items = positions
+            .into_iter()
+            .map(|pos| {
+                last = Some(pos);
+                items.item(&pos)
+            })
+            .collect();
+
+        Self { items, last }
+    }
+
+    /// The `Items` of this page.
+    pub fn items(&self) -> &[&'a T] {
+        &self.items
+    }
+
+    pub fn into_items(self) -> Vec<&'a T> {
+        self.items
+    }
+
+    /// `true`, if the page has no `Items`: the end is reached.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The [`Cursor`] for the next page, or `None` if the page is empty.
+    pub fn cursor(&self) -> Option<Cursor> {
+        self.last.map(Cursor)
+    }
+
+    /// The [`KeyCursor`] for the next page, or `None` if the page is empty.
+    /// The `Key` is read from the last `Item` of this page with the given function.
+    pub fn key_cursor<K, F>(&self, key: F) -> Option<KeyCursor<K>>
+    where
+        F: FnOnce(&T) -> K,
+    {
+        let position = self.last?;
+        let item = self.items.last()?;
+        Some(KeyCursor {
+            key: key(item),
+            position,
+        })
+    }
+}
+
+/// Skip `offset` and take maximal `limit` positions from the position lists,
+/// whole lists are skipped without iterating the positions.
+pub(crate) fn slices_page<'a, X, It>(
+    slices: It,
+    offset: usize,
+    limit: usize,
+) -> impl Iterator<Item = &'a X>
+where
+    It: Iterator<Item = &'a [X]>,
+    X: 'a,
+{
+    slices
+        .scan((offset, limit), |(skip, take), s| {
+            if *take == 0 {
+                return None;
+            }
+            let n = (*skip).min(s.len());
+            *skip -= n;
+            let s = &s[n..];
+            let s = &s[..(*take).min(s.len())];
+            *take -= s.len();
+            Some(s)
+        })
+        .flatten()
+}
+
+/// All positions after the given cursor, the `positions` must be sorted.
+pub(crate) fn after(positions: &[usize], cursor: Option<Cursor>) -> &[usize] {
+    match cursor {
+        Some(Cursor(last)) => &positions[positions.partition_point(|p| *p <= last)..],
+        None => positions,
+    }
+}
+
+/// The next [`Page`] in `Key` order (ties by position), which starts after the given [`KeyCursor`].
+pub(crate) fn by_key<'a, F, I>(
+    filter: &F,
+    items: &'a I,
+    cursor: Option<&KeyCursor<F::Key>>,
+    limit: usize,
+) -> Page<'a, I::Output>
+where
+    F: Ordered<Index = usize>,
+    I: Indexable<usize> + ?Sized,
+{
+    match cursor {
+        Some(KeyCursor { key, position }) => {
+            let same_key = after(filter.get(key), Some(Cursor(*position)));
+            let next_keys = filter.range((Bound::Excluded(key), Bound::Unbounded));
+            Page::new(
+                items,
+                std::iter::once(same_key)
+                    .chain(next_keys)
+                    .flatten()
+                    .take(limit)
+                    .copied(),
+            )
+        }
+        None => Page::new(items, filter.range(..).flatten().take(limit).copied()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        collections::rw::IList,
+        index::{MapIndex, MultiIntIndex},
+    };
+    use rstest::rstest;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Car(i32, &'static str);
+
+    #[rstest]
+    #[case::all(0, 10, vec![1, 2, 3, 4, 5])]
+    #[case::skip_first_slice(2, 2, vec![3, 4])]
+    #[case::within_slice(1, 1, vec![2])]
+    #[case::over_slices(1, 3, vec![2, 3, 4])]
+    #[case::end(4, 3, vec![5])]
+    #[case::after_end(5, 3, vec![])]
+    #[case::no_limit(0, 0, vec![])]
+    fn slices(#[case] offset: usize, #[case] limit: usize, #[case] expected: Vec<usize>) {
+        let slices: [&[usize]; 4] = [&[1, 2], &[], &[3], &[4, 5]];
+        assert_eq!(
+            expected,
+            slices_page(slices.into_iter(), offset, limit)
+                .copied()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn after_cursor() {
+        let positions = [1, 3, 5];
+        assert_eq!([1, 3, 5], after(&positions, None));
+        assert_eq!([5], after(&positions, Some(Cursor(3))));
+        assert_eq!([3, 5], after(&positions, Some(Cursor(2))));
+        assert!(after(&positions, Some(Cursor(5))).is_empty());
+    }
+
+    #[test]
+    fn cursor_valid_after_push() {
+        let mut l = IList::<MapIndex<&str>, _, _>::from_vec(
+            |c: &Car| c.1,
+            vec![Car(1, "BMW"), Car(2, "VW"), Car(3, "BMW"), Car(4, "BMW")],
+        );
+
+        let page = l.idx().filter_after(|f| f.eq(&"BMW"), None, 2);
+        assert_eq!(&[&Car(1, "BMW"), &Car(3, "BMW")], page.items());
+        let cursor = page.cursor();
+        assert_eq!(Some(Cursor(2)), cursor);
+
+        l.push(Car(5, "BMW"));
+        l.push(Car(6, "VW"));
+
+        let page = l.idx().filter_after(|f| f.eq(&"BMW"), cursor, 2);
+        assert_eq!(&[&Car(4, "BMW"), &Car(5, "BMW")], page.items());
+
+        let page = l.idx().filter_after(|f| f.eq(&"BMW"), page.cursor(), 2);
+        assert!(page.is_empty());
+        assert_eq!(None, page.cursor());
+    }
+
+    #[test]
+    fn key_cursor_valid_after_push() {
+        let mut l = IList::<MultiIntIndex, _, _>::from_vec(
+            |c: &Car| c.0,
+            vec![Car(3, "BMW"), Car(-1, "VW"), Car(3, "Audi"), Car(7, "BMW")],
+        );
+
+        let page = l.idx().page_by_key(None, 2);
+        assert_eq!(&[&Car(-1, "VW"), &Car(3, "BMW")], page.items());
+        let cursor = page.key_cursor(|c| c.0);
+        assert_eq!(
+            Some(KeyCursor {
+                key: 3,
+                position: 0
+            }),
+            cursor
+        );
+
+        l.push(Car(3, "Opel"));
+        l.push(Car(-5, "Seat"));
+
+        let page = l.idx().page_by_key(cursor.as_ref(), 3);
+        assert_eq!(
+            &[&Car(3, "Audi"), &Car(3, "Opel"), &Car(7, "BMW")],
+            page.items()
+        );
+
+        let cursor = page.key_cursor(|c| c.0);
+        assert!(l.idx().page_by_key(cursor.as_ref(), 3).is_empty());
+    }
+
+    #[test]
+    fn view_page_by_key() {
+        let l = IList::<MultiIntIndex, _, _>::from_vec(
+            |c: &Car| c.0,
+            vec![
+                Car(3, "BMW"),
+                Car(-1, "VW"),
+                Car(3, "Audi"),
+                Car(7, "BMW"),
+                Car(-5, "Opel"),
+            ],
+        );
+        let view = l.idx().create_view([7, 3, -5]);
+
+        let page = view.page_by_key(None, 2);
+        assert_eq!(&[&Car(-5, "Opel"), &Car(3, "BMW")], page.items());
+
+        let cursor = page.key_cursor(|c| c.0);
+        let page = view.page_by_key(cursor.as_ref(), 2);
+        assert_eq!(&[&Car(3, "Audi"), &Car(7, "BMW")], page.items());
+
+        let cursor = page.key_cursor(|c| c.0);
+        assert!(view.page_by_key(cursor.as_ref(), 2).is_empty());
+    }
+
+    #[test]
+    fn offset_limit() {
+        let l = IList::<MapIndex<&str>, _, _>::from_vec(
+            |c: &Car| c.1,
+            vec![Car(1, "BMW"), Car(2, "VW"), Car(3, "BMW"), Car(4, "Audi")],
+        );
+
+        assert_eq!(
+            vec![&Car(3, "BMW")],
+            l.idx()
+                .filter_page(|f| f.eq(&"BMW"), 1, 5)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&Car(3, "BMW"), &Car(4, "Audi")],
+            l.idx()
+                .get_many_page(["BMW", "Audi", "VW"], 1, 2)
+                .collect::<Vec<_>>()
+        );
+
+        let view = l.idx().create_view(["BMW", "VW"]);
+        assert_eq!(
+            vec![&Car(2, "VW")],
+            view.get_many_page(["BMW", "Audi", "VW"], 2, 2)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&Car(1, "BMW")],
+            view.filter_page(|f| f.eq(&"BMW") | f.eq(&"VW"), 0, 1)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&Car(3, "BMW")],
+            view.filter_after(|f| f.eq(&"BMW"), Some(Cursor(0)), 5)
+                .into_items()
+        );
+    }
+}
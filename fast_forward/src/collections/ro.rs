@@ -1,14 +1,26 @@
 //! read-only collections.
 //!
-use std::{collections::HashMap, hash::Hash, marker::PhantomData, ops::Deref};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    marker::PhantomData,
+    ops::Deref,
+    sync::{Arc, RwLock},
+};
 
+#[cfg(feature = "serde")]
+use crate::index::store::Positions;
+#[cfg(feature = "rayon")]
+use crate::index::{par, store::Merge};
 use crate::{
     collections::Retriever,
     index::{
-        store::{Store, ToStore},
+        store::{Filterable, Indexer, Store, ToIndexer, ToStore},
         Indexable,
     },
 };
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// [`IList`] is a read only indexed `List` (Vec, Array, ..., default is a Vec) which owned the given items.
 ///
@@ -46,7 +58,6 @@ use crate::{
 ///     vec![&Person::new(0, "Paul"), &Person::new(2, "Jasmin")],
 /// );
 /// ```
-
 pub struct IList<S, T, L = Vec<T>> {
     store: S,
     items: L,
@@ -55,7 +66,6 @@ pub struct IList<S, T, L = Vec<T>> {
 
 impl<S, T, L> IList<S, T, L>
 where
-    S: Store<Index = usize>,
     L: Indexable<usize, Output = T>,
 {
     pub fn new<F, K>(field: F, items: L) -> Self
@@ -71,9 +81,94 @@ where
         }
     }
 
-    pub fn idx(&self) -> Retriever<'_, S, L> {
+    /// Create an `IList` with a tuple of `Stores` and a tuple of functions for the `Keys` (see: [`Indexer`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::{MapIndex, UniqueUIntIndex}, collections::ro::IList};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// pub struct Car(usize, String);
+    ///
+    /// let l = IList::<(UniqueUIntIndex, MapIndex), _>::new_multi(
+    ///     (|c: &Car| c.0, |c: &Car| c.1.clone()),
+    ///     vec![Car(1, "BMW".into()), Car(2, "VW".into())],
+    /// );
+    ///
+    /// assert_eq!(Some(&Car(2, "VW".into())), l.idx_by(|(ids, _)| ids).get(&2).next());
+    /// assert_eq!(Some(&Car(1, "BMW".into())), l.idx_by(|(_, names)| names).get(&"BMW".into()).next());
+    /// ```
+    pub fn new_multi<F>(fields: F, items: L) -> Self
+    where
+        S: Indexer<T, F, Index = usize>,
+        L: ToIndexer<usize, T>,
+    {
+        Self {
+            store: items.to_indexer(&fields),
+            items,
+            _type: PhantomData,
+        }
+    }
+
+    /// Create an `IList`, the `Store` is created in parallel (see: [`crate::index::par`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MapIndex, collections::ro::IList};
+    ///
+    /// let l = IList::<MapIndex<usize>, _>::par_new(|i| i % 3, (0..10_000).collect::<Vec<_>>());
+    ///
+    /// assert_eq!(3334, l.idx().get(&0).count());
+    /// assert_eq!(Some(&9_998), l.idx().get(&2).last());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_new<F, K>(field: F, items: L) -> Self
+    where
+        F: Fn(&T) -> K + Sync,
+        S: Store<Key = K, Index = usize> + Merge + Send,
+        L: AsRef<[T]>,
+        T: Sync,
+    {
+        Self {
+            store: par::to_indexer(items.as_ref(), &field),
+            items,
+            _type: PhantomData,
+        }
+    }
+
+    /// Create an `IList` with a tuple of `Stores`, the `Stores` are created in parallel (see: [`crate::index::par`]).
+    #[cfg(feature = "rayon")]
+    pub fn par_new_multi<F>(fields: F, items: L) -> Self
+    where
+        S: Indexer<T, F, Index = usize> + Merge + Send,
+        F: Sync,
+        L: AsRef<[T]>,
+        T: Sync,
+    {
+        Self {
+            store: par::to_indexer(items.as_ref(), &fields),
+            items,
+            _type: PhantomData,
+        }
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, L>
+    where
+        S: Filterable,
+    {
         Retriever::new(&self.store, &self.items)
     }
+
+    /// Returns the [`Retriever`] for the selected `Store`, if the `IList` has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, L>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        Retriever::new(select(&self.store), &self.items)
+    }
 }
 
 impl<S, T, L> Deref for IList<S, T, L> {
@@ -127,10 +222,7 @@ pub struct IRefList<'l, S, T> {
     items: &'l [T],
 }
 
-impl<'l, S, T> IRefList<'l, S, T>
-where
-    S: Store<Index = usize>,
-{
+impl<'l, S, T> IRefList<'l, S, T> {
     pub fn new<F, K>(field: F, items: &'l [T]) -> Self
     where
         F: Fn(&T) -> K,
@@ -142,9 +234,60 @@ where
         }
     }
 
-    pub fn idx(&self) -> Retriever<'_, S, &'l [T]> {
+    /// Create an `IRefList` with a tuple of `Stores` and a tuple of functions for the `Keys` (see: [`Indexer`]).
+    pub fn new_multi<F>(fields: F, items: &'l [T]) -> Self
+    where
+        S: Indexer<T, F, Index = usize>,
+    {
+        Self {
+            store: items.to_indexer(&fields),
+            items,
+        }
+    }
+
+    /// Create an `IRefList`, the `Store` is created in parallel (see: [`crate::index::par`]).
+    #[cfg(feature = "rayon")]
+    pub fn par_new<F, K>(field: F, items: &'l [T]) -> Self
+    where
+        F: Fn(&T) -> K + Sync,
+        S: Store<Key = K, Index = usize> + Merge + Send,
+        T: Sync,
+    {
+        Self {
+            store: par::to_indexer(items, &field),
+            items,
+        }
+    }
+
+    /// Create an `IRefList` with a tuple of `Stores`, the `Stores` are created in parallel (see: [`crate::index::par`]).
+    #[cfg(feature = "rayon")]
+    pub fn par_new_multi<F>(fields: F, items: &'l [T]) -> Self
+    where
+        S: Indexer<T, F, Index = usize> + Merge + Send,
+        F: Sync,
+        T: Sync,
+    {
+        Self {
+            store: par::to_indexer(items, &fields),
+            items,
+        }
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, &'l [T]>
+    where
+        S: Filterable,
+    {
         Retriever::new(&self.store, &self.items)
     }
+
+    /// Returns the [`Retriever`] for the selected `Store`, if the `IRefList` has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, &'l [T]>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        Retriever::new(select(&self.store), &self.items)
+    }
 }
 
 impl<S, T> Deref for IRefList<'_, S, T> {
@@ -203,7 +346,6 @@ pub struct IMap<S, X, T, M = HashMap<X, T>> {
 
 impl<S, X, T, M> IMap<S, X, T, M>
 where
-    S: Store<Index = X>,
     M: Indexable<X>,
 {
     pub fn new<F, K>(field: F, items: M) -> Self
@@ -221,9 +363,36 @@ where
         }
     }
 
-    pub fn idx(&self) -> Retriever<'_, S, M> {
+    /// Create an `IMap` with a tuple of `Stores` and a tuple of functions for the `Keys` (see: [`Indexer`]).
+    pub fn new_multi<F>(fields: F, items: M) -> Self
+    where
+        S: Indexer<T, F, Index = X>,
+        X: Eq + Hash + Clone,
+        M: ToIndexer<X, T>,
+    {
+        Self {
+            store: items.to_indexer(&fields),
+            items,
+            _idx: PhantomData,
+            _type: PhantomData,
+        }
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, M>
+    where
+        S: Filterable,
+    {
         Retriever::new(&self.store, &self.items)
     }
+
+    /// Returns the [`Retriever`] for the selected `Store`, if the `IMap` has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, M>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        Retriever::new(select(&self.store), &self.items)
+    }
 }
 
 impl<S, X, T, M> Deref for IMap<S, X, T, M> {
@@ -234,6 +403,122 @@ impl<S, X, T, M> Deref for IMap<S, X, T, M> {
     }
 }
 
+/// [`SwapList`] holds the current version of a read only [`IList`], which can be replaced (hot-swap) atomically.
+///
+/// A reader gets an [`Arc`] of the current `IList` (`load`) and executes the queries without holding a lock.
+/// A new `IList` can be built in the background (e.g. in an other thread) and swapped in with `swap`.
+/// A reader, which holds the old `IList`, is not disturbed, the old `IList` is dropped with the last reader.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::{index::MapIndex, collections::ro::{IList, SwapList}};
+///
+/// let l = SwapList::new(IList::<MapIndex<&str>, _>::new(|s| *s, vec!["a", "b"]));
+///
+/// let old = l.load();
+/// assert!(old.idx().contains(&"a"));
+///
+/// // build a new IList and swap it in
+/// let new = std::thread::spawn(|| IList::new(|s: &&str| *s, vec!["c"])).join().unwrap();
+/// l.swap(new);
+///
+/// // the old IList is not changed
+/// assert!(old.idx().contains(&"a"));
+///
+/// assert!(!l.load().idx().contains(&"a"));
+/// assert!(l.load().idx().contains(&"c"));
+/// ```
+pub struct SwapList<S, T, L = Vec<T>> {
+    current: RwLock<Arc<IList<S, T, L>>>,
+}
+
+impl<S, T, L> SwapList<S, T, L> {
+    pub fn new(list: IList<S, T, L>) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(list)),
+        }
+    }
+
+    /// Returns the current `IList`.
+    pub fn load(&self) -> Arc<IList<S, T, L>> {
+        Arc::clone(&self.current.read().expect("list lock poisoned"))
+    }
+
+    /// Replace the current `IList` with the given one and returns the replaced `IList`.
+    pub fn swap(&self, list: IList<S, T, L>) -> Arc<IList<S, T, L>> {
+        let list = Arc::new(list);
+        std::mem::replace(
+            &mut *self.current.write().expect("list lock poisoned"),
+            list,
+        )
+    }
+}
+
+impl<S, T, L> From<IList<S, T, L>> for SwapList<S, T, L> {
+    fn from(list: IList<S, T, L>) -> Self {
+        Self::new(list)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+#[serde(rename = "IList")]
+struct IListRef<'a, S, L> {
+    store: &'a S,
+    items: &'a L,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "IList")]
+struct IListData<S, L> {
+    store: S,
+    items: L,
+}
+
+/// Serialize the `Store(s)` and the `Items`.
+#[cfg(feature = "serde")]
+impl<S, T, L> Serialize for IList<S, T, L>
+where
+    S: Serialize,
+    L: Serialize,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        IListRef {
+            store: &self.store,
+            items: &self.items,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialize the `Store(s)` and the `Items`, all positions in the `Store(s)` must exist in the `Items`.
+///
+/// ## Hint:
+/// Only the positions are validated. The functions for the `Keys` are not serialized,
+/// so it is not checked, whether the `Keys` in the `Store(s)` are the `Keys` of the `Items`.
+#[cfg(feature = "serde")]
+impl<'de, S, T, L> Deserialize<'de> for IList<S, T, L>
+where
+    S: Deserialize<'de> + Positions,
+    L: Deserialize<'de> + AsRef<[T]>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let IListData { store, items } = IListData::<S, L>::deserialize(deserializer)?;
+        if !store.check_positions(items.as_ref().len()) {
+            return Err(de::Error::custom(
+                "the store contains a position, which is out of bounds",
+            ));
+        }
+        Ok(Self {
+            store,
+            items,
+            _type: PhantomData,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
@@ -528,4 +813,134 @@ mod tests {
         assert_eq!(Some(&Car(2, "VW".into())), it.next());
         assert_eq!(None, it.next());
     }
+
+    #[rstest]
+    fn read_only_multi_stores(cars: Vec<Car>) {
+        let l = IRefList::<'_, (MultiUIntIndex, MapIndex), _>::new_multi(
+            (Car::id, |c: &Car| c.1.clone()),
+            &cars,
+        );
+
+        // combine two indices: id and name
+        let idxs = l.idx_by(|(ids, _)| ids).eq(&2) & l.idx_by(|(_, names)| names).eq(&"VW".into());
+        assert_eq!(
+            vec![&Car(2, "VW".into())],
+            idxs.items(&cars).collect::<Vec<_>>()
+        );
+
+        let m = cars
+            .iter()
+            .map(|c| (c.1.clone(), Car(c.0, c.1.clone())))
+            .collect::<HashMap<_, _>>();
+        let m =
+            IMap::<(MultiUIntIndex<usize, String>, MapIndex<String, String>), _, Car>::new_multi(
+                (Car::id, |c: &Car| c.1.clone()),
+                m,
+            );
+        assert_eq!(
+            Some(&Car(5, "Audi".into())),
+            m.idx_by(|(ids, _)| ids).get(&5).next()
+        );
+        assert!(m.idx_by(|(_, names)| names).contains(&"Porsche".into()));
+    }
+
+    #[rstest]
+    fn filter_with_residual_only_on_candidates(cars: Vec<Car>) {
+        let l = IList::<MultiUIntIndex, _>::new(Car::id, cars);
+
+        let calls = std::cell::Cell::new(0);
+        let r = l
+            .idx()
+            .filter_with(
+                |f| f.eq(&2),
+                |c| {
+                    calls.set(calls.get() + 1);
+                    c.1 == "VW"
+                },
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(vec![&Car(2, "VW".into())], r);
+        assert_eq!(2, calls.get());
+
+        // on a view
+        let view = l.idx().create_view([2, 99]);
+        let r = view
+            .filter_with(|f| f.eq(&2) | f.eq(&99), |c| c.1 != "VW")
+            .collect::<Vec<_>>();
+        assert_eq!(vec![&Car(2, "BMW".into()), &Car(99, "Porsche".into())], r);
+    }
+
+    #[rstest]
+    fn count_without_items(cars: Vec<Car>) {
+        let l = IList::<MultiUIntIndex, _>::new(Car::id, cars);
+
+        assert_eq!(2, l.idx().count(&2));
+        assert_eq!(0, l.idx().count(&1000));
+        assert_eq!(4, l.idx().count_many([2, 5, 99, 1000]));
+        assert_eq!(3, l.idx().count_filter(|f| f.eq(&2) | f.eq(&5)));
+        assert!(l.idx().exists_any([1000, 99]));
+        assert!(!l.idx().exists_any([1000, 1]));
+
+        let view = l.idx().create_view([2, 99]);
+        assert_eq!(2, view.count(&2));
+        assert_eq!(0, view.count(&5));
+        assert_eq!(3, view.count_many([2, 5, 99]));
+        assert_eq!(1, view.count_filter(|f| f.eq(&5) | f.eq(&99)));
+        assert!(view.exists_any([5, 99]));
+        assert!(!view.exists_any([5, 1000]));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[rstest]
+    fn parallel(cars: Vec<Car>) {
+        use rayon::prelude::*;
+
+        let l = IList::<MultiUIntIndex, _>::par_new(Car::id, cars);
+        assert_eq!(
+            l.idx().get_many([99, 2, 1000, 5]).collect::<Vec<_>>(),
+            l.idx().par_get_many([99, 2, 1000, 5]).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["BMW", "VW", "Porsche"],
+            l.idx()
+                .par_filter(|f| f.eq(&2) | f.eq(&99))
+                .map(|c| c.1.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, l.idx().par_get_many([]).count());
+
+        let l = IRefList::<(MultiUIntIndex, MapIndex), _>::par_new_multi(
+            (Car::id, |c: &Car| c.1.clone()),
+            &l,
+        );
+        assert_eq!(
+            vec![&Car(5, "Audi".into())],
+            l.idx_by(|(_, names)| names)
+                .par_get_many(["Audi".into(), "Opel".into()])
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    fn swap_list(cars: Vec<Car>) {
+        let l = SwapList::new(IList::<MultiUIntIndex, _>::new(Car::id, cars));
+        let old = l.load();
+
+        std::thread::scope(|s| {
+            // rebuild in the background, while the old IList is read
+            s.spawn(|| {
+                l.swap(IList::new(Car::id, vec![Car(7, "Opel".into())]));
+            });
+            s.spawn(|| assert_eq!(2, old.idx().get(&2).count()));
+        });
+
+        assert_eq!(2, old.idx().get(&2).count());
+        let current = l.load();
+        assert!(!current.idx().contains(&2));
+        assert_eq!(Some(&Car(7, "Opel".into())), current.idx().get(&7).next());
+
+        let replaced = l.swap(IList::new(Car::id, vec![]));
+        assert!(Arc::ptr_eq(&current, &replaced));
+        assert!(l.load().is_empty());
+    }
 }
@@ -0,0 +1,402 @@
+//! Thread-safe read-write list for many concurrent readers and writers (read-copy-update).
+//!
+//! A reader gets a [`Snapshot`] (an [`Arc`] of the current version of the `Items` and `Stores`)
+//! and executes the queries without holding a lock. So a long-running query never blocks a writer.
+//!
+//! A writer changes the current version under a write lock. If readers hold a [`Snapshot`]
+//! of the current version, the `Items` and the `Stores` are copied first (see: [`crate::collections::rw::snapshot`]).
+//! The writers are serialized and a new [`Snapshot`] sees all completed writes.
+//!
+//! ## Cost of a write
+//!
+//! While a reader holds a [`Snapshot`] of the current version, the next write copies
+//! all `Items` and all `Stores`, this is O(n) for every such write (not only the first).
+//! So many changes should be batched in one [`ConcurrentList::write`]
+//! and `Snapshots` should be released (dropped) as soon as possible.
+//! Without a held `Snapshot`, a write changes the current version in place.
+//!
+//! ## Positions and Keys
+//!
+//! A position can be changed by a write of another thread between two calls.
+//! So prefer the `Key` of the primary `Store` ([`ConcurrentList::update_by_primary`],
+//! [`ConcurrentList::remove_by_primary`]) or look up the position and change the `Item`
+//! in one [`ConcurrentList::write`].
+//!
+//! ## Send and Sync
+//!
+//! [`ConcurrentList`] and [`Snapshot`] are `Send` and `Sync`, if the `Stores`, the `Items`
+//! and the key function are `Send` and `Sync`. So a `ConcurrentList` can be shared
+//! between threads, e.g. with an [`Arc`] or a scoped thread ([`std::thread::scope`]).
+//!
+use std::{
+    ops::Deref,
+    sync::{Arc, RwLock, RwLockWriteGuard},
+};
+
+use crate::{
+    collections::rw::{list::IList, snapshot::Snapshot, RemoveMode, Upsert},
+    index::store::Indexer,
+};
+
+#[derive(Debug)]
+struct Current<S, I, F> {
+    list: Arc<IList<S, I, F>>,
+    version: u64,
+}
+
+/// [`ConcurrentList`] is a thread-safe read write indexed `List`.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::{index::MapIndex, collections::rw::concurrent::ConcurrentList};
+///
+/// let l = ConcurrentList::<MapIndex<String>, String, _>::new(|s| s.clone());
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| l.push("a".into()));
+///     s.spawn(|| l.push("b".into()));
+/// });
+///
+/// let r = l.read();
+/// assert_eq!(2, r.len());
+/// assert!(r.idx().contains(&"a".into()));
+///
+/// // many operations in one write
+/// l.write(|l| {
+///     // the Index of the Store, which is valid for every RemoveMode
+///     let index = l.idx().eq(&"a".into()).as_slice()[0];
+///     l.update_index(index, |s| *s = "c".into());
+///     l.remove_by_primary(&"b".into());
+/// });
+///
+/// // the snapshot is not changed
+/// assert_eq!(2, r.len());
+/// assert_eq!(&["c"], &l.read()[..]);
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentList<S, I, F> {
+    current: RwLock<Current<S, I, F>>,
+}
+
+impl<S, I, F> ConcurrentList<S, I, F>
+where
+    S: Indexer<I, F, Index = usize> + Clone,
+    I: Clone,
+    F: Clone,
+{
+    pub fn new(field: F) -> Self {
+        Self::from_list(IList::new(field))
+    }
+
+    pub fn from_vec(field: F, v: Vec<I>) -> Self {
+        Self::from_list(IList::from_vec(field, v))
+    }
+
+    fn from_list(list: IList<S, I, F>) -> Self {
+        Self {
+            current: RwLock::new(Current {
+                list: Arc::new(list),
+                version: 0,
+            }),
+        }
+    }
+
+    /// Set the [`RemoveMode`] for this List.
+    pub fn with_remove_mode(self, mode: RemoveMode) -> Self {
+        let current = self.current.into_inner().expect("list lock poisoned");
+        Self::from_list(IList::unwrap_or_clone(current.list).with_remove_mode(mode))
+    }
+
+    /// Returns a [`Snapshot`] of the current version, for executing queries without a lock.
+    pub fn read(&self) -> Snapshot<S, I, F> {
+        let current = self.current.read().expect("list lock poisoned");
+        Snapshot::new(Arc::clone(&current.list), current.version)
+    }
+
+    fn lock(&self) -> RwLockWriteGuard<'_, Current<S, I, F>> {
+        self.current.write().expect("list lock poisoned")
+    }
+
+    /// Execute all changes of the given function as one write (atomic for the readers).
+    pub fn write<W, R>(&self, write: W) -> R
+    where
+        W: FnOnce(&mut Writer<'_, S, I, F>) -> R,
+    {
+        let mut current = self.lock();
+        current.version += 1;
+        write(&mut Writer(IList::make_mut(&mut current.list)))
+    }
+
+    /// Append a new `Item` to the List and returns the position.
+    pub fn push(&self, item: I) -> usize {
+        self.write(|l| l.push(item))
+    }
+
+    /// Insert the `Item`, if the `Key` of the primary `Store` is new, otherwise the `Item` is replaced.
+    pub fn upsert(&self, item: I) -> Upsert {
+        self.write(|l| l.upsert(item))
+    }
+
+    /// Update the item on the given position, returns `false`, if the position doesn't exist.
+    pub fn update<U>(&self, pos: usize, update: U) -> bool
+    where
+        U: FnMut(&mut I),
+    {
+        self.write(|l| l.update(pos, update).is_some())
+    }
+
+    /// The Item on the given position will be removed from the list.
+    pub fn remove(&self, pos: usize) -> Option<I> {
+        self.write(|l| l.remove(pos))
+    }
+
+    /// Update the `Item` with the given `Key` of the primary `Store`,
+    /// returns `false`, if the `Key` doesn't exist.
+    pub fn update_by_primary<U>(&self, key: &S::PrimaryKey, update: U) -> bool
+    where
+        U: FnMut(&mut I),
+    {
+        self.write(|l| l.update_by_primary(key, update).is_some())
+    }
+
+    /// Remove the `Item` with the given `Key` of the primary `Store`.
+    pub fn remove_by_primary(&self, key: &S::PrimaryKey) -> Option<I> {
+        self.write(|l| l.remove_by_primary(key))
+    }
+
+    /// The current version, every write creates a new version.
+    pub fn version(&self) -> u64 {
+        self.current.read().expect("list lock poisoned").version
+    }
+}
+
+/// The List in a [`ConcurrentList::write`], with all changing operations of an [`IList`],
+/// but without registering observers (a copy doesn't take the observers).
+pub struct Writer<'a, S, I, F>(&'a mut IList<S, I, F>);
+
+impl<S, I, F> Writer<'_, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    /// Append a new `Item` to the List and returns the position.
+    pub fn push(&mut self, item: I) -> usize {
+        self.0.push(item)
+    }
+
+    /// Append all `Items` to the List.
+    pub fn extend<It>(&mut self, iter: It)
+    where
+        It: IntoIterator<Item = I>,
+    {
+        self.0.extend(iter)
+    }
+
+    /// Insert the `Item` on the given position, all `Items` after are shifted to the right.
+    pub fn insert(&mut self, pos: usize, item: I) {
+        self.0.insert(pos, item)
+    }
+
+    /// Insert the `Item`, if the `Key` of the primary `Store` is new, otherwise the `Item` is replaced.
+    pub fn upsert(&mut self, item: I) -> Upsert {
+        self.0.upsert(item)
+    }
+
+    /// Call `insert`, if the `Key` of the primary `Store` is new, otherwise call `update`.
+    pub fn upsert_with<N, U>(&mut self, key: &S::PrimaryKey, insert: N, update: U) -> Upsert
+    where
+        N: FnOnce() -> I,
+        U: FnMut(&mut I),
+    {
+        self.0.upsert_with(key, insert, update)
+    }
+
+    /// Update the item on the given position.
+    pub fn update<U>(&mut self, pos: usize, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.0.update(pos, update)
+    }
+
+    /// The Item on the given position will be removed from the list.
+    pub fn remove(&mut self, pos: usize) -> Option<I> {
+        self.0.remove(pos)
+    }
+
+    /// Update the `Item` with the given `Key` of the primary `Store`.
+    pub fn update_by_primary<U>(&mut self, key: &S::PrimaryKey, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.0.update_by_primary(key, update)
+    }
+
+    /// Remove the `Item` with the given `Key` of the primary `Store`.
+    pub fn remove_by_primary(&mut self, key: &S::PrimaryKey) -> Option<I> {
+        self.0.remove_by_primary(key)
+    }
+
+    /// Update the item on the given `Index` of the `Store`.
+    pub fn update_index<U>(&mut self, index: usize, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.0.update_index(index, update)
+    }
+
+    /// The Item on the given `Index` of the `Store` will be removed.
+    pub fn remove_index(&mut self, index: usize) -> Option<I> {
+        self.0.remove_index(index)
+    }
+
+    /// Retains only the `Items` specified by the predicate, the order is preserved.
+    pub fn retain<P>(&mut self, predicate: P)
+    where
+        P: FnMut(&I) -> bool,
+    {
+        self.0.retain(predicate)
+    }
+
+    /// Removes all `Items`.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Remove all tombstones and rewrite the positions in the `Store`.
+    pub fn compact(&mut self) {
+        self.0.compact()
+    }
+}
+
+impl<S, I, F> Deref for Writer<'_, S, I, F> {
+    type Target = IList<S, I, F>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::{MapIndex, UniqueUIntIndex};
+    use std::thread;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Car(usize, String);
+
+    #[test]
+    fn send_and_sync() {
+        fn check<T: Send + Sync>() {}
+        type Cars = ConcurrentList<UniqueUIntIndex, Car, fn(&Car) -> usize>;
+        check::<Cars>();
+        check::<Snapshot<UniqueUIntIndex, Car, fn(&Car) -> usize>>();
+    }
+
+    #[test]
+    fn stress_readers_and_writers() {
+        const WRITERS: usize = 4;
+        const ITEMS: usize = 200;
+
+        let l = ConcurrentList::<(UniqueUIntIndex, MapIndex), Car, _>::new((
+            |c: &Car| c.0,
+            |c: &Car| c.1.clone(),
+        ));
+
+        thread::scope(|s| {
+            for w in 0..WRITERS {
+                let l = &l;
+                s.spawn(move || {
+                    for i in 0..ITEMS {
+                        let id = w * ITEMS + i;
+                        l.push(Car(id, format!("car-{id}")));
+
+                        // every second car is renamed and every fourth car is removed
+                        if i % 2 == 1 {
+                            assert!(l.update_by_primary(&id, |c| c.1 = format!("renamed-{id}")));
+                        }
+                        if i % 4 == 3 {
+                            l.write(|l| {
+                                let pos = l.iter().position(|c| c.0 == id).unwrap();
+                                assert_eq!(id, l.remove(pos).unwrap().0);
+                            });
+                        }
+                    }
+                });
+            }
+
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let mut version = 0;
+                    while version < (WRITERS * ITEMS) as u64 {
+                        let r = l.read();
+                        assert!(r.version() >= version);
+                        version = r.version();
+
+                        // every snapshot is consistent: the items and the stores
+                        let ids = r.idx_by(|(ids, _)| ids);
+                        let names = r.idx_by(|(_, names)| names);
+                        for c in r.iter() {
+                            assert_eq!(Some(c), ids.get(&c.0).next());
+                            assert_eq!(Some(c), names.get(&c.1).next());
+                        }
+                        thread::yield_now();
+                    }
+                });
+            }
+        });
+
+        let r = l.read();
+        assert_eq!(WRITERS * ITEMS * 3 / 4, r.len());
+        let names = r.idx_by(|(_, names)| names);
+        for id in 0..WRITERS * ITEMS {
+            let i = id % ITEMS;
+            let removed = i % 4 == 3;
+            assert_eq!(!removed, r.idx_by(|(ids, _)| ids).contains(&id));
+            assert_eq!(
+                !removed && i % 2 == 1,
+                names.contains(&format!("renamed-{id}"))
+            );
+            assert_eq!(i.is_multiple_of(2), names.contains(&format!("car-{id}")));
+        }
+    }
+
+    #[test]
+    fn snapshot_isolation() {
+        let l = ConcurrentList::<UniqueUIntIndex, _, _>::from_vec(
+            |c: &Car| c.0,
+            vec![Car(1, "BMW".into()), Car(2, "VW".into())],
+        )
+        .with_remove_mode(RemoveMode::Tombstone);
+
+        let before = l.read();
+        assert_eq!(0, before.version());
+
+        thread::scope(|s| {
+            s.spawn(|| l.remove(0));
+            s.spawn(|| l.upsert(Car(3, "Audi".into())));
+        });
+        assert_eq!(2, l.version());
+        assert!(l.update(0, |c| c.1 = "Opel".into()));
+        assert!(!l.update(9, |c| c.1 = "Opel".into()));
+
+        // the old snapshot still sees the old version
+        assert_eq!(
+            vec![&Car(1, "BMW".into())],
+            before.idx().get(&1).collect::<Vec<_>>()
+        );
+        assert!(!before.idx().contains(&3));
+
+        let after = l.read();
+        assert!(!after.idx().contains(&1));
+        assert_eq!(Some(&Car(2, "Opel".into())), after.idx().get(&2).next());
+        assert!(after.idx().contains(&3));
+
+        assert_eq!(Some(Car(3, "Audi".into())), l.remove_by_primary(&3));
+        assert_eq!(None, l.remove_by_primary(&3));
+        assert!(!l.update_by_primary(&3, |c| c.1 = "Opel".into()));
+        assert!(after.idx().contains(&3));
+        assert!(!l.read().idx().contains(&3));
+    }
+}
@@ -0,0 +1,190 @@
+//! Entry API for [`crate::collections::rw::IList`], like [`std::collections::hash_map::Entry`].
+//!
+//! An [`Entry`] is found by a `Key` of a selected `Store`. An occupied `Entry` returns an [`ItemMut`],
+//! which updates all `Stores`, when it is dropped. So no second lookup and no `update` call is necessary.
+//!
+use crate::{
+    collections::rw::list_base::{ItemMut, List},
+    index::store::Indexer,
+};
+
+/// A view into a single `Item` of a List, which is either occupied or vacant.
+pub enum Entry<'a, S, I, F> {
+    Occupied(OccupiedEntry<'a, S, I, F>),
+    Vacant(VacantEntry<'a, S, I, F>),
+}
+
+impl<'a, S, I, F> Entry<'a, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    pub(crate) fn new(list: &'a mut List<S, I, F>, pos: Option<usize>) -> Self {
+        match pos {
+            Some(pos) => Entry::Occupied(OccupiedEntry { list, pos }),
+            None => Entry::Vacant(VacantEntry { list }),
+        }
+    }
+
+    /// Insert the given `Item`, if the `Entry` is vacant and returns a mutable reference to the `Item`.
+    pub fn or_insert(self, item: I) -> ItemMut<'a, S, I, F> {
+        self.or_insert_with(|| item)
+    }
+
+    /// Insert the `Item` from the given function, if the `Entry` is vacant and returns a mutable reference to the `Item`.
+    pub fn or_insert_with<N>(self, insert: N) -> ItemMut<'a, S, I, F>
+    where
+        N: FnOnce() -> I,
+    {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(insert()),
+        }
+    }
+
+    /// Call the `update` function, if the `Entry` is occupied.
+    pub fn and_modify<U>(self, update: U) -> Self
+    where
+        U: FnOnce(&mut I),
+    {
+        match self {
+            Entry::Occupied(e) => {
+                update(&mut e.list.get_mut_at(e.pos).expect("occupied position"));
+                Entry::Occupied(e)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+}
+
+/// An occupied `Entry`, the `Key` exists in the selected `Store`.
+pub struct OccupiedEntry<'a, S, I, F> {
+    list: &'a mut List<S, I, F>,
+    pos: usize,
+}
+
+impl<'a, S, I, F> OccupiedEntry<'a, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    /// The position of the `Item` in the List.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn get(&self) -> &I {
+        &self.list[self.pos]
+    }
+
+    /// Returns a mutable reference to the `Item`, all `Stores` are updated, when the reference is dropped.
+    pub fn get_mut(&mut self) -> ItemMut<'_, S, I, F> {
+        self.list.get_mut_at(self.pos).expect("occupied position")
+    }
+
+    /// Converts the `Entry` into a mutable reference to the `Item`.
+    pub fn into_mut(self) -> ItemMut<'a, S, I, F> {
+        self.list.get_mut_at(self.pos).expect("occupied position")
+    }
+
+    /// Remove the `Item` from the List (depends on the [`crate::collections::rw::RemoveMode`]).
+    pub fn remove(self) -> I {
+        self.list.remove_at(self.pos).expect("occupied position")
+    }
+}
+
+/// A vacant `Entry`, the `Key` doesn't exist in the selected `Store`.
+pub struct VacantEntry<'a, S, I, F> {
+    list: &'a mut List<S, I, F>,
+}
+
+impl<'a, S, I, F> VacantEntry<'a, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    /// Append the `Item` to the List and returns a mutable reference to the `Item`.
+    pub fn insert(self, item: I) -> ItemMut<'a, S, I, F> {
+        let pos = self.list.len();
+        self.list.push(item);
+        self.list.get_mut_at(pos).expect("pushed position")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collections::rw::{entry::Entry, IList, RemoveMode},
+        index::{MapIndex, MultiUIntIndex, UniqueUIntIndex},
+    };
+    use rstest::rstest;
+
+    #[derive(Debug, PartialEq)]
+    struct Car(usize, &'static str, usize);
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn entry(#[case] mode: RemoveMode) {
+        let mut l = IList::<(UniqueUIntIndex, MapIndex<&str>), _, _>::from_vec(
+            (|c: &Car| c.0, |c: &Car| c.1),
+            vec![Car(1, "BMW", 10), Car(2, "VW", 20), Car(3, "Audi", 30)],
+        )
+        .with_remove_mode(mode);
+        l.remove(0);
+
+        // occupied: modify in place
+        {
+            let mut c = l.entry(|(ids, _)| ids, &3).or_insert(Car(3, "Opel", 0));
+            c.1 = "Porsche";
+            c.2 += 1;
+        }
+        let names = l.idx_by(|(_, names)| names);
+        assert!(!names.contains(&"Audi"));
+        assert_eq!(Some(&Car(3, "Porsche", 31)), names.get(&"Porsche").next());
+
+        // vacant: insert
+        let pos = l
+            .entry(|(_, names)| names, &"Seat")
+            .or_insert_with(|| Car(4, "Seat", 40))
+            .position();
+        assert_eq!(2, pos);
+        assert_eq!(
+            Some(&Car(4, "Seat", 40)),
+            l.idx_by(|(ids, _)| ids).get(&4).next()
+        );
+
+        // and_modify
+        l.entry(|(ids, _)| ids, &2)
+            .and_modify(|c| c.0 = 5)
+            .or_insert(Car(2, "VW", 0));
+        assert!(!l.idx_by(|(ids, _)| ids).contains(&2));
+        assert_eq!(
+            Some(&Car(5, "VW", 20)),
+            l.idx_by(|(ids, _)| ids).get(&5).next()
+        );
+
+        // remove
+        match l.entry(|(_, names)| names, &"VW") {
+            Entry::Occupied(e) => assert_eq!(Car(5, "VW", 20), e.remove()),
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert!(!l.idx_by(|(ids, _)| ids).contains(&5));
+        assert_eq!(2, l.len());
+    }
+
+    #[test]
+    fn entry_single_store() {
+        let mut l = IList::<MultiUIntIndex, _, _>::from_vec(|c: &Car| c.2, vec![Car(1, "BMW", 10)]);
+
+        match l.entry(|s| s, &10) {
+            Entry::Occupied(mut e) => {
+                assert_eq!(0, e.position());
+                assert_eq!(&Car(1, "BMW", 10), e.get());
+                e.get_mut().2 = 11;
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert!(!l.idx().contains(&10));
+        assert_eq!(Some(&Car(1, "BMW", 11)), l.idx().get(&11).next());
+
+        assert!(matches!(l.entry(|s| s, &10), Entry::Vacant(_)));
+    }
+}
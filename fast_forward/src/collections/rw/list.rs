@@ -1,14 +1,35 @@
 //! read-write list ([`Vec`]).
 //!
-use std::{fmt::Debug, ops::Deref};
+use std::{
+    fmt::Debug,
+    ops::{Deref, RangeBounds},
+    sync::Arc,
+};
 
+#[cfg(feature = "rayon")]
+use crate::index::store::Merge;
+#[cfg(feature = "serde")]
+use crate::index::store::Validate;
 use crate::{
     collections::{
-        rw::{list_base::List, Editable, Editor},
+        rw::{
+            entry::Entry,
+            list_base::{ItemList, ItemMut, IterMut, List},
+            observer::{Event, ObserverId},
+            oplog::OpLog,
+            transaction::Transaction,
+            Editor, RemoveMode, Upsert,
+        },
         Retriever,
     },
-    index::store::Store,
+    index::{
+        indices::Indices,
+        store::{Filterable, Indexer, Store},
+        Filter, Indexable,
+    },
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// [`IList`] is a read write indexed `List` which owned the given items.
 ///
@@ -52,8 +73,7 @@ pub struct IList<S, I, F>(List<S, I, F>);
 
 impl<S, I, F> IList<S, I, F>
 where
-    S: Store<Index = usize>,
-    F: Fn(&I) -> S::Key,
+    S: Indexer<I, F, Index = usize>,
 {
     pub fn new(field: F) -> Self {
         Self(List::new(field))
@@ -63,6 +83,29 @@ where
         Self(List::from_vec(field, v))
     }
 
+    /// Create an `IList`, the `Stores` are created in parallel (see: [`crate::index::par`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MapIndex, collections::rw::IList};
+    ///
+    /// let mut l = IList::<MapIndex<usize>, _, _>::par_from_vec(|i| i % 3, (0..10_000).collect());
+    /// assert_eq!(3334, l.idx().get(&0).count());
+    ///
+    /// l.push(10_000);
+    /// assert_eq!(Some(&10_000), l.idx().get(&1).last());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_from_vec(field: F, v: Vec<I>) -> Self
+    where
+        S: Merge + Send,
+        I: Sync,
+        F: Sync,
+    {
+        Self(List::par_from_vec(field, v))
+    }
+
     pub fn from_iter<It>(field: F, iter: It) -> Self
     where
         It: IntoIterator<Item = I> + ExactSizeIterator,
@@ -75,31 +118,552 @@ where
         self.0.push(item)
     }
 
-    /// Update the item on the given position.
+    /// Insert the `Item`, if the `Key` of the primary (the first) `Store` is new,
+    /// otherwise the `Item` with this `Key` is replaced. All `Stores` are updated.
+    ///
+    /// ## Hint:
+    /// The primary `Store` must be a unique `Store` (see: [`crate::index::store::Indexer`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{
+    ///     index::{MapIndex, UniqueUIntIndex},
+    ///     collections::rw::{IList, Upsert},
+    /// };
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, &'static str);
+    ///
+    /// let mut l = IList::<(UniqueUIntIndex, MapIndex<&str>), _, _>::from_vec(
+    ///     (|c: &Car| c.0, |c: &Car| c.1),
+    ///     vec![Car(1, "BMW"), Car(2, "VW")],
+    /// );
+    ///
+    /// assert_eq!(Upsert::Updated(1), l.upsert(Car(2, "Audi")));
+    /// assert_eq!(Upsert::Inserted(2), l.upsert(Car(3, "VW")));
+    /// assert_eq!(Some(&Car(3, "VW")), l.idx_by(|(_, names)| names).get(&"VW").next());
+    ///
+    /// let r = l.upsert_with(&1, || Car(1, "Opel"), |c| c.1 = "Porsche");
+    /// assert_eq!(Upsert::Updated(0), r);
+    /// assert_eq!(Some(&Car(1, "Porsche")), l.idx_by(|(_, names)| names).get(&"Porsche").next());
+    /// ```
+    pub fn upsert(&mut self, item: I) -> Upsert {
+        self.0.upsert(item)
+    }
+
+    /// Call `insert`, if the `Key` of the primary (the first) `Store` is new,
+    /// otherwise call `update` with the `Item` for this `Key`.
+    pub fn upsert_with<N, U>(&mut self, key: &S::PrimaryKey, insert: N, update: U) -> Upsert
+    where
+        N: FnOnce() -> I,
+        U: FnMut(&mut I),
+    {
+        self.0.upsert_with(key, insert, update)
+    }
+
+    /// Update the `Item` with the given `Key` of the primary (the first) `Store`.
+    /// In contrast to a position, the `Key` doesn't change by other `inserts` or `removes`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::{MapIndex, UniqueUIntIndex}, collections::rw::IList};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, &'static str);
+    ///
+    /// let mut l = IList::<(UniqueUIntIndex, MapIndex<&str>), _, _>::from_vec(
+    ///     (|c: &Car| c.0, |c: &Car| c.1),
+    ///     vec![Car(1, "BMW"), Car(2, "VW"), Car(3, "Audi")],
+    /// );
+    ///
+    /// assert_eq!(Some(&Car(2, "Opel")), l.update_by_primary(&2, |c| c.1 = "Opel"));
+    /// assert_eq!(Some(Car(1, "BMW")), l.remove_by_primary(&1));
+    /// assert_eq!(None, l.remove_by_primary(&1));
+    ///
+    /// assert_eq!(&[Car(3, "Audi"), Car(2, "Opel")], &l[..]);
+    /// assert!(l.idx_by(|(_, names)| names).contains(&"Opel"));
+    /// ```
+    pub fn update_by_primary<U>(&mut self, key: &S::PrimaryKey, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.0.update_by_primary(key, update)
+    }
+
+    /// Remove the `Item` with the given `Key` of the primary (the first) `Store`.
+    pub fn remove_by_primary(&mut self, key: &S::PrimaryKey) -> Option<I> {
+        self.0.remove_by_primary(key)
+    }
+
+    /// Update the item on the given position in the List (the position of `l[pos]`).
+    ///
+    /// ## Hint:
+    /// By the [`RemoveMode::Tombstone`], use [`IList::update_index`] for the `Indices` of the `Store`.
     pub fn update<U>(&mut self, pos: usize, update: U) -> Option<&I>
     where
         U: FnMut(&mut I),
     {
-        self.0.update(pos, update)
+        self.0.update_at(pos, update)
     }
 
-    /// The Item in the list will be removed.
+    /// The Item on the given position in the List (the position of `l[pos]`) will be removed.
     ///
     /// ## Hint:
-    /// The remove is a swap_remove ([`std::vec::Vec::swap_remove`])
+    /// The remove depends on the [`RemoveMode`], the default is a swap_remove ([`std::vec::Vec::swap_remove`]).
+    /// By the [`RemoveMode::Tombstone`], use [`IList::remove_index`] for the `Indices` of the `Store`.
     pub fn remove(&mut self, pos: usize) -> Option<I> {
-        self.0.remove(pos)
+        self.0.remove_at(pos)
+    }
+
+    /// Update the item on the given `Index` of the `Store` (e.g. from `idx().eq(..)`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MapIndex, collections::rw::{IList, RemoveMode}};
+    ///
+    /// let mut l = IList::<MapIndex<&str>, _, _>::from_vec(|s| *s, vec!["A", "B", "C"])
+    ///     .with_remove_mode(RemoveMode::Tombstone);
+    /// l.remove(0);
+    ///
+    /// let index = *l.idx().eq(&"C").as_slice().first().unwrap();
+    /// assert_eq!(2, index);
+    /// assert_eq!(Some(1), l.position(index));
+    ///
+    /// l.update_index(index, |s| *s = "D");
+    /// assert_eq!(&["B", "D"], &l[..]);
+    ///
+    /// assert_eq!(Some("D"), l.remove_index(index));
+    /// assert_eq!(&["B"], &l[..]);
+    /// ```
+    pub fn update_index<U>(&mut self, index: usize, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.0.update_index(index, update)
+    }
+
+    /// The Item on the given `Index` of the `Store` (e.g. from `idx().eq(..)`) will be removed.
+    pub fn remove_index(&mut self, index: usize) -> Option<I> {
+        self.0.remove_index(index)
+    }
+
+    /// Returns the position in the List for the given `Index` of the `Store`,
+    /// `None`, if the `Index` is removed or doesn't exist.
+    pub fn position(&self, index: usize) -> Option<usize> {
+        self.0.position(index)
+    }
+
+    /// Iterate over all `Items` with the `Index` of the `Store`.
+    /// By the [`RemoveMode::Tombstone`], the `Index` is not the position in the List.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MapIndex, collections::rw::{IList, RemoveMode}};
+    ///
+    /// let mut l = IList::<MapIndex<&str>, _, _>::from_vec(|s| *s, vec!["A", "B", "C"])
+    ///     .with_remove_mode(RemoveMode::Tombstone);
+    /// l.remove(1);
+    ///
+    /// assert_eq!(vec![(0, &"A"), (2, &"C")], l.iter_indexed().collect::<Vec<_>>());
+    /// // the Indices of the Store are mapped to the Items
+    /// assert_eq!(vec![&"C"], l.idx().eq(&"C").items(&l).collect::<Vec<_>>());
+    /// ```
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &I)> {
+        self.0.iter_indexed()
+    }
+
+    /// Append all `Items` to the List.
+    pub fn extend<It>(&mut self, iter: It)
+    where
+        It: IntoIterator<Item = I>,
+    {
+        self.0.extend(iter)
+    }
+
+    /// Insert the `Item` on the given position, all `Items` after are shifted to the right
+    /// and only their positions are rewritten in the `Store` (the tombstones are compacted).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos > len`.
+    pub fn insert(&mut self, pos: usize, item: I) {
+        self.0.insert(pos, item)
+    }
+
+    /// Retains only the `Items` specified by the predicate, the order is preserved.
+    /// The positions of the shifted `Items` are rewritten once, instead of after every removed `Item`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::UniqueUIntIndex, collections::rw::IList};
+    ///
+    /// let mut l = IList::<UniqueUIntIndex, _, _>::from_vec(|i| *i, vec![1, 2, 3, 4, 5]);
+    /// l.retain(|i| i % 2 == 1);
+    /// assert_eq!(&[1, 3, 5], &l[..]);
+    /// assert!(!l.idx().contains(&2));
+    ///
+    /// assert_eq!(vec![3, 5], l.drain(1..));
+    /// l.insert(0, 0);
+    /// l.extend([7, 8]);
+    /// assert_eq!(&[0, 1, 7, 8], &l[..]);
+    /// assert_eq!(Some(&7), l.idx().get(&7).next());
+    ///
+    /// l.truncate(1);
+    /// assert_eq!(&[0], &l[..]);
+    /// l.clear();
+    /// assert!(l.is_empty());
+    /// assert!(!l.idx().contains(&0));
+    /// ```
+    pub fn retain<P>(&mut self, predicate: P)
+    where
+        P: FnMut(&I) -> bool,
+    {
+        self.0.retain(predicate)
+    }
+
+    /// Removes the `Items` in the given range and returns the removed `Items`, the order is preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds (like [`std::vec::Vec::drain`]).
+    pub fn drain<R>(&mut self, range: R) -> Vec<I>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.0.drain(range)
+    }
+
+    /// Shortens the List to the given `len`.
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len)
     }
 
-    pub fn idx(&self) -> Retriever<'_, S, Vec<I>> {
+    /// Removes all `Items`.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Set the [`RemoveMode`] for this List.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MapIndex, collections::rw::{IList, RemoveMode}};
+    ///
+    /// let mut l = IList::<MapIndex<&str>, _, _>::from_vec(|s| *s, vec!["A", "B", "C", "D"])
+    ///     .with_remove_mode(RemoveMode::Tombstone);
+    ///
+    /// l.remove(1);
+    /// l.remove(0);
+    /// assert_eq!(&["C", "D"], &l[..]);
+    /// assert_eq!(2, l.tombstones());
+    /// assert_eq!(Some(&"D"), l.idx().get(&"D").next());
+    ///
+    /// l.compact();
+    /// assert_eq!(0, l.tombstones());
+    /// assert_eq!(Some(&"D"), l.idx().get(&"D").next());
+    /// ```
+    pub fn with_remove_mode(mut self, mode: RemoveMode) -> Self {
+        self.0.set_remove_mode(mode);
+        self
+    }
+
+    pub fn remove_mode(&self) -> RemoveMode {
+        self.0.remove_mode()
+    }
+
+    /// The number of tombstones (removed positions), which are not compacted
+    /// (only by [`RemoveMode::Tombstone`]).
+    pub fn tombstones(&self) -> usize {
+        self.0.tombstones()
+    }
+
+    /// Remove all tombstones and rewrite the positions in the `Store`.
+    pub fn compact(&mut self) {
+        self.0.compact()
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, ItemList<I>>
+    where
+        S: Filterable,
+    {
         self.0.idx()
     }
 
-    pub fn idx_mut(&mut self) -> Editor<'_, I, List<S, I, F>> {
+    /// Returns the [`Retriever`] for the selected `Store`, if the List has a tuple of `Stores`.
+    /// All `Stores` are updated by every change of the List.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::{MapIndex, MultiUIntIndex}, collections::rw::IList};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, &'static str);
+    ///
+    /// let mut l = IList::<(MultiUIntIndex, MapIndex<&str>), _, _>::from_vec(
+    ///     (|c: &Car| c.0, |c: &Car| c.1),
+    ///     vec![Car(1, "BMW"), Car(2, "VW")],
+    /// );
+    ///
+    /// l.update(0, |c| c.1 = "Audi");
+    /// l.push(Car(1, "Opel"));
+    ///
+    /// let (ids, names) = (l.idx_by(|(ids, _)| ids), l.idx_by(|(_, names)| names));
+    /// assert_eq!(vec![&Car(1, "Audi"), &Car(1, "Opel")], ids.get(&1).collect::<Vec<_>>());
+    /// assert!(!names.contains(&"BMW"));
+    /// assert_eq!(Some(&Car(1, "Audi")), names.get(&"Audi").next());
+    /// ```
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, ItemList<I>>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        self.0.idx_by(select)
+    }
+
+    /// Returns a mutable reference (a guard) to the first `Item` with the given `Key`.
+    /// All `Stores` are updated with the changed `Keys`, when the guard is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::{MapIndex, UniqueUIntIndex}, collections::rw::IList};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, &'static str);
+    ///
+    /// let mut l = IList::<UniqueUIntIndex, _, _>::from_vec(|c| c.0, vec![Car(1, "BMW"), Car(2, "VW")]);
+    ///
+    /// if let Some(mut car) = l.get_mut(&2) {
+    ///     car.0 = 3;
+    ///     car.1 = "Audi";
+    /// }
+    /// assert!(!l.idx().contains(&2));
+    /// assert_eq!(Some(&Car(3, "Audi")), l.idx().get(&3).next());
+    /// ```
+    pub fn get_mut(&mut self, key: &S::Key) -> Option<ItemMut<'_, S, I, F>>
+    where
+        S: Filterable<Index = usize>,
+    {
+        self.0.get_mut_by(|s| s, key)
+    }
+
+    /// Returns a mutable reference (a guard) to the first `Item` with the given `Key` of the selected `Store`.
+    pub fn get_mut_by<R, Select>(
+        &mut self,
+        select: Select,
+        key: &R::Key,
+    ) -> Option<ItemMut<'_, S, I, F>>
+    where
+        R: Filterable<Index = usize>,
+        Select: FnOnce(&S) -> &R,
+    {
+        self.0.get_mut_by(select, key)
+    }
+
+    /// Returns a mutable reference (a guard) to the `Item` on the given position.
+    pub fn get_mut_at(&mut self, pos: usize) -> Option<ItemMut<'_, S, I, F>> {
+        self.0.get_mut_at(pos)
+    }
+
+    /// Returns an [`IterMut`] over mutable references (guards) to the `Items` of the filter result.
+    pub fn filter_mut<P>(&mut self, predicate: P) -> IterMut<'_, S, I, F>
+    where
+        S: Filterable<Index = usize>,
+        P: for<'x> Fn(&Filter<'x, S, ItemList<I>>) -> Indices<'x, usize>,
+    {
+        self.0.filter_mut(predicate)
+    }
+
+    /// Get the [`Entry`] for the given `Key` of the selected `Store`, for in-place modification or insertion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::{MapIndex, UniqueUIntIndex}, collections::rw::IList};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Car(usize, &'static str);
+    ///
+    /// let mut l = IList::<(UniqueUIntIndex, MapIndex<&str>), _, _>::from_vec(
+    ///     (|c: &Car| c.0, |c: &Car| c.1),
+    ///     vec![Car(1, "BMW"), Car(2, "VW")],
+    /// );
+    ///
+    /// // the Stores are updated, when the reference is dropped
+    /// l.entry(|(ids, _)| ids, &2).or_insert(Car(2, "VW")).1 = "Audi";
+    /// l.entry(|(ids, _)| ids, &3).or_insert(Car(3, "Opel"));
+    ///
+    /// assert_eq!(Some(&Car(2, "Audi")), l.idx_by(|(_, names)| names).get(&"Audi").next());
+    /// assert!(!l.idx_by(|(_, names)| names).contains(&"VW"));
+    /// assert_eq!(Some(&Car(3, "Opel")), l.idx_by(|(ids, _)| ids).get(&3).next());
+    /// ```
+    pub fn entry<R, Select>(&mut self, select: Select, key: &R::Key) -> Entry<'_, S, I, F>
+    where
+        R: Filterable<Index = usize>,
+        Select: FnOnce(&S) -> &R,
+    {
+        self.0.entry(select, key)
+    }
+
+    /// Register an observer, which is called with an [`Event`] after every change of an `Item`.
+    /// Returns an [`ObserverId`], to remove the observer with `unsubscribe`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use fast_forward::{index::MapIndex, collections::rw::{observer::Event, IList}};
+    ///
+    /// let mut l = IList::<MapIndex<&str>, _, _>::from_vec(|s| *s, vec!["a", "b", "c"]);
+    ///
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let log2 = log.clone();
+    /// let id = l.subscribe(move |e| log2.lock().unwrap().push(format!("{e:?}")));
+    ///
+    /// l.update(1, |s| *s = "x");
+    /// l.remove(0);
+    /// assert_eq!(
+    ///     vec![
+    ///         r#"Updated { index: 1, old: "b", new: "x" }"#,
+    ///         r#"Removed { index: 0, item: "a" }"#,
+    ///         "Moved { from: 2, to: 0 }",
+    ///     ],
+    ///     *log.lock().unwrap()
+    /// );
+    ///
+    /// assert!(l.unsubscribe(id));
+    /// l.push("d");
+    /// assert_eq!(3, log.lock().unwrap().len());
+    /// ```
+    pub fn subscribe<O>(&mut self, observer: O) -> ObserverId
+    where
+        O: FnMut(&Event<'_, I, usize>) + Send + Sync + 'static,
+        I: Clone,
+    {
+        self.0.subscribe(observer)
+    }
+
+    /// Remove the observer with the given id, returns `false`, if the observer doesn't exist.
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        self.0.unsubscribe(id)
+    }
+
+    /// Apply all `Ops` of the given [`OpLog`], to rebuild the same `Items` (in the same order)
+    /// with the same `Key` lookups, independent of the [`RemoveMode`] of this List.
+    /// The tombstones are not reproduced, so the `Indices` of the `Stores` depend on the [`RemoveMode`] of this List.
+    pub fn replay(&mut self, log: &OpLog<I>)
+    where
+        I: Clone,
+    {
+        self.0.replay(log)
+    }
+
+    /// Start a [`Transaction`], which stages `push`, `update` and `remove` operations.
+    /// By `commit` all operations are applied or none (if a `Key` of a unique `Store` is not unique).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{
+    ///     index::{MapIndex, UniqueUIntIndex},
+    ///     collections::rw::{transaction::TransactionError, IList},
+    /// };
+    ///
+    /// #[derive(Debug, PartialEq, Clone)]
+    /// struct Car(usize, &'static str);
+    ///
+    /// let mut l = IList::<(UniqueUIntIndex, MapIndex<&str>), _, _>::from_vec(
+    ///     (|c: &Car| c.0, |c: &Car| c.1),
+    ///     vec![Car(1, "BMW"), Car(2, "VW")],
+    /// );
+    ///
+    /// // the id 2 exists already
+    /// let mut t = l.transaction();
+    /// t.push(Car(3, "Audi"));
+    /// t.update(0, |c| c.0 = 2);
+    /// assert_eq!(Err(TransactionError::NotUnique), t.commit());
+    /// assert_eq!(&[Car(1, "BMW"), Car(2, "VW")], &l[..]);
+    ///
+    /// // the id 2 is free, after the remove of VW
+    /// let mut t = l.transaction();
+    /// t.push(Car(3, "Audi"));
+    /// t.update(0, |c| c.0 = 2);
+    /// t.remove(1);
+    /// assert_eq!(Ok(()), t.commit());
+    /// assert_eq!(&[Car(2, "BMW"), Car(3, "Audi")], &l[..]);
+    /// assert_eq!(Some(&Car(2, "BMW")), l.idx_by(|(ids, _)| ids).get(&2).next());
+    /// ```
+    pub fn transaction(&mut self) -> Transaction<'_, S, I, F> {
+        Transaction::new(&mut self.0)
+    }
+
+    pub fn idx_mut(&mut self) -> Editor<'_, I, List<S, I, F>>
+    where
+        S: Store<Index = usize>,
+        F: Fn(&I) -> S::Key,
+    {
         Editor::new(&mut self.0)
     }
 }
 
+impl<S, I, F> IList<S, I, F>
+where
+    S: Clone,
+    I: Clone,
+    F: Clone,
+{
+    /// Returns a copy of the `Items` and the `Stores` (O(n)), the registered observers are not copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MapIndex, collections::rw::IList};
+    ///
+    /// let mut l = IList::<MapIndex<&str>, _, _>::from_vec(|s| *s, vec!["a"]);
+    /// l.subscribe(|_| panic!("only called by changes of l"));
+    ///
+    /// let mut copy = l.clone_without_observers();
+    /// copy.push("b");
+    /// assert!(copy.idx().contains(&"b"));
+    /// assert!(!l.idx().contains(&"b"));
+    /// ```
+    pub fn clone_without_observers(&self) -> Self {
+        Self(self.0.clone_without_observers())
+    }
+
+    /// Like [`Arc::make_mut`], the shared `IList` is copied (without the observers), before it is changed.
+    pub(crate) fn make_mut(list: &mut Arc<Self>) -> &mut Self {
+        if Arc::get_mut(list).is_none() {
+            *list = Arc::new(list.clone_without_observers());
+        }
+        Arc::get_mut(list).expect("the list is not shared")
+    }
+
+    /// Like [`Arc::unwrap_or_clone`], the shared `IList` is copied without the observers.
+    pub(crate) fn unwrap_or_clone(list: Arc<Self>) -> Self {
+        Arc::try_unwrap(list).unwrap_or_else(|l| l.clone_without_observers())
+    }
+}
+
+/// Maps the `Indices` of the `Stores` to the `Items` (with the tombstones, see: [`RemoveMode::Tombstone`]).
+impl<S, I, F> Indexable<usize> for IList<S, I, F> {
+    type Output = I;
+
+    fn item(&self, idx: &usize) -> &Self::Output {
+        self.0.item(idx)
+    }
+}
+
+/// The `Items` without the tombstones: the position in the slice is the position in the List,
+/// which is not the `Index` of the `Stores` by the [`RemoveMode::Tombstone`].
+/// So use the `IList` (not the slice) for mapping `Indices` to `Items` (e.g. `indices.items(&l)`).
 impl<S, I, F> Deref for IList<S, I, F> {
     type Target = [I];
 
@@ -108,10 +672,60 @@ impl<S, I, F> Deref for IList<S, I, F> {
     }
 }
 
+/// Serialize the `Store(s)`, the `Items`, the tombstones and the [`RemoveMode`] (without the observers).
+#[cfg(feature = "serde")]
+impl<S, I, F> Serialize for IList<S, I, F>
+where
+    S: Serialize,
+    I: Serialize,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, I, F> IList<S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    /// Deserialize an `IList` with the given function(s) for the `Keys`, the `Stores` are validated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{index::MapIndex, collections::rw::IList};
+    ///
+    /// let l = IList::<MapIndex, _, _>::from_vec(|s: &String| s.clone(), vec!["a".into(), "b".into()]);
+    /// let json = serde_json::to_string(&l).unwrap();
+    ///
+    /// let mut l = IList::<MapIndex, String, _>::deserialize_with(
+    ///     |s: &String| s.clone(),
+    ///     &mut serde_json::Deserializer::from_str(&json),
+    /// )
+    /// .unwrap();
+    /// assert!(l.idx().contains(&"b".into()));
+    ///
+    /// l.push("c".into());
+    /// assert_eq!(&["a", "b", "c"], &l[..]);
+    /// ```
+    pub fn deserialize_with<'de, D>(field: F, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        S: Deserialize<'de> + Validate<I, F>,
+        I: Deserialize<'de>,
+    {
+        List::deserialize_with(field, deserializer).map(Self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::index::{MapIndex, MultiIntIndex, MultiUIntIndex};
+    use crate::{
+        collections::rw::transaction::TransactionError,
+        index::{MapIndex, MultiIntIndex, MultiUIntIndex, UniqueIntIndex},
+    };
     use rstest::{fixture, rstest};
 
     #[derive(PartialEq, Debug, Clone)]
@@ -129,6 +743,21 @@ mod tests {
         }
     }
 
+    impl std::fmt::Display for Person {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{},{}", self.id, self.name)
+        }
+    }
+
+    impl std::str::FromStr for Person {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (id, name) = s.split_once(',').ok_or(())?;
+            Ok(Person::new(id.parse().map_err(|_| ())?, name))
+        }
+    }
+
     #[test]
     fn check() {
         let mut l = IList::<MultiIntIndex, Person, _>::new(|p| p.id);
@@ -508,7 +1137,7 @@ mod tests {
         assert_eq!(4, cars.len());
 
         assert_eq!(Some(Car(2, "BMW".into())), cars.remove(0));
-        assert_eq!(&Car(99, "Porsche".into()), cars.get(0).unwrap());
+        assert_eq!(&Car(99, "Porsche".into()), cars.first().unwrap());
 
         // after delete: 1 Car
         let r = cars.idx().get(&2).collect::<Vec<_>>();
@@ -565,4 +1194,512 @@ mod tests {
         );
         assert!(updated.is_empty());
     }
+
+    #[rstest]
+    #[case::shift(RemoveMode::Shift, 0)]
+    #[case::tombstone(RemoveMode::Tombstone, 2)]
+    fn remove_order_preserving(
+        cars: Vec<Car>,
+        #[case] mode: RemoveMode,
+        #[case] tombstones: usize,
+    ) {
+        let mut cars =
+            IList::<MultiUIntIndex, _, _>::from_vec(|c| c.0, cars).with_remove_mode(mode);
+        assert_eq!(mode, cars.remove_mode());
+
+        assert_eq!(Some(Car(5, "Audi".into())), cars.remove(1));
+        assert_eq!(Some(Car(2, "BMW".into())), cars.remove(0));
+        assert_eq!(None, cars.remove(2));
+        assert_eq!(tombstones, cars.tombstones());
+
+        // iteration is in insertion order
+        assert_eq!(
+            vec![&Car(2, "VW".into()), &Car(99, "Porsche".into())],
+            cars.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&Car(2, "VW".into())],
+            cars.idx().get(&2).collect::<Vec<_>>()
+        );
+        assert!(!cars.idx().contains(&5));
+
+        // push, update and remove by key after remove
+        assert_eq!(2, cars.push(Car(5, "Opel".into())));
+        assert_eq!(Some(&Car(2, "Opel".into())), cars.update(2, |c| c.0 = 2));
+        assert_eq!(
+            vec![&Car(2, "VW".into()), &Car(2, "Opel".into())],
+            cars.idx().get(&2).collect::<Vec<_>>()
+        );
+        cars.idx_mut().update_by_key(&99, |c| c.1.push('!'));
+        assert_eq!(&Car(99, "Porsche!".into()), &cars[1]);
+
+        cars.compact();
+        assert_eq!(0, cars.tombstones());
+        assert_eq!(
+            vec![&Car(2, "VW".into()), &Car(2, "Opel".into())],
+            cars.idx().get(&2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&Car(99, "Porsche!".into())),
+            cars.idx().get(&99).next()
+        );
+
+        cars.idx_mut().remove_by_key(&2);
+        assert_eq!(
+            vec![&Car(99, "Porsche!".into())],
+            cars.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&Car(99, "Porsche!".into())),
+            cars.idx().get(&99).next()
+        );
+    }
+
+    #[rstest]
+    fn switch_remove_mode_compacts(cars: Vec<Car>) {
+        let mut cars = IList::<MultiUIntIndex, _, _>::from_vec(|c| c.0, cars)
+            .with_remove_mode(RemoveMode::Tombstone);
+        cars.remove(0);
+        assert_eq!(1, cars.tombstones());
+
+        let mut cars = cars.with_remove_mode(RemoveMode::Swap);
+        assert_eq!(0, cars.tombstones());
+        assert_eq!(Some(Car(5, "Audi".into())), cars.remove(0));
+        assert_eq!(
+            vec![&Car(99, "Porsche".into()), &Car(2, "VW".into())],
+            cars.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&Car(99, "Porsche".into())), cars.idx().get(&99).next());
+    }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::shift(RemoveMode::Shift)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn observer_mirror(#[case] mode: RemoveMode) {
+        use crate::collections::rw::observer::Event;
+        use std::sync::{Arc, Mutex};
+
+        let mut l = IList::<(UniqueIntIndex, MapIndex), _, _>::from_vec(
+            (|p: &Person| p.id, |p: &Person| p.name.clone()),
+            vec![
+                Person::new(0, "Paul"),
+                Person::new(-2, "Mario"),
+                Person::new(2, "Jasmin"),
+            ],
+        )
+        .with_remove_mode(mode);
+
+        // a copy of the List, which is changed by the events
+        let mirror = Arc::new(Mutex::new(l.to_vec()));
+        let m = mirror.clone();
+        l.subscribe(move |e| {
+            let mut m = m.lock().unwrap();
+            match e {
+                Event::Inserted { index, item } => m.insert(*index, (*item).clone()),
+                Event::Updated { index, old, new } => {
+                    assert_eq!(&m[*index], *old);
+                    m[*index] = (*new).clone();
+                }
+                Event::Removed { index, item } => assert_eq!(m.remove(*index), **item),
+                Event::Moved { from, to } => {
+                    // the last Item (before the remove) is moved to the removed position
+                    assert_eq!(RemoveMode::Swap, mode);
+                    assert_eq!(*from, m.len());
+                    let last = m.pop().unwrap();
+                    m.insert(*to, last);
+                }
+            }
+        });
+
+        l.push(Person::new(5, "Egon"));
+        l.update(1, |p| p.name = "Inge".into());
+        l.get_mut_at(0).unwrap().id = 9;
+        l.remove(0);
+        l.insert(1, Person::new(6, "Peter"));
+        l.upsert(Person::new(2, "Jas"));
+        l.retain(|p| p.id != 6);
+        assert_eq!(*mirror.lock().unwrap(), l.to_vec());
+
+        let mut t = l.transaction();
+        t.update(0, |p| p.id = 7);
+        t.remove(1);
+        t.push(Person::new(8, "Otto"));
+        t.commit().unwrap();
+        assert_eq!(*mirror.lock().unwrap(), l.to_vec());
+
+        l.drain(1..);
+        assert_eq!(*mirror.lock().unwrap(), l.to_vec());
+        l.clear();
+        assert!(mirror.lock().unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::shift(RemoveMode::Shift)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn shift_positions(#[case] mode: RemoveMode) {
+        let mut l = IList::<(UniqueIntIndex, MapIndex), _, _>::from_vec(
+            (|p: &Person| p.id, |p: &Person| p.name.clone()),
+            (0..8).map(|id| Person::new(id, "Paul")).collect(),
+        )
+        .with_remove_mode(mode);
+
+        // every Item is found by its Keys on its position
+        let check = |l: &IList<_, Person, _>| {
+            let ids = l.idx_by(|(ids, _)| ids);
+            for p in l.iter() {
+                assert_eq!(Some(p), ids.get(&p.id).next());
+            }
+            let names = l.idx_by(|(_, names)| names);
+            assert_eq!(l.len(), names.get(&"Paul".into()).count());
+        };
+
+        l.remove(1);
+        l.insert(2, Person::new(10, "Paul"));
+        check(&l);
+        assert_eq!(0, l.tombstones());
+
+        l.remove(0);
+        l.retain(|p| p.id % 2 == 0);
+        check(&l);
+        if mode != RemoveMode::Swap {
+            assert_eq!(
+                vec![2, 10, 4, 6],
+                l.iter().map(|p| p.id).collect::<Vec<_>>()
+            );
+        }
+
+        l.remove(3);
+        l.drain(..1);
+        check(&l);
+        assert_eq!(0, l.tombstones());
+        if mode != RemoveMode::Swap {
+            assert_eq!(vec![10, 4], l.iter().map(|p| p.id).collect::<Vec<_>>());
+        }
+    }
+
+    #[rstest]
+    fn oplog_replay(
+        #[values(RemoveMode::Swap, RemoveMode::Shift, RemoveMode::Tombstone)] mode: RemoveMode,
+        #[values(RemoveMode::Swap, RemoveMode::Shift, RemoveMode::Tombstone)]
+        replay_mode: RemoveMode,
+    ) {
+        use crate::collections::rw::oplog::OpLog;
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(OpLog::new()));
+        let new_list = |mode| {
+            IList::<(UniqueIntIndex, MapIndex), Person, _>::new((
+                |p: &Person| p.id,
+                |p: &Person| p.name.clone(),
+            ))
+            .with_remove_mode(mode)
+        };
+
+        let mut l = new_list(mode);
+        l.subscribe(OpLog::recorder(&log));
+        l.extend([
+            Person::new(0, "Paul"),
+            Person::new(-2, "Mario"),
+            Person::new(2, "Jasmin"),
+            Person::new(3, "Inge"),
+        ]);
+        l.update(1, |p| p.name = "Egon".into());
+        l.remove(0);
+        l.insert(1, Person::new(6, "Peter"));
+        l.get_mut_at(2).unwrap().id = 9;
+        l.retain(|p| p.id != 6);
+        l.push(Person::new(7, "Otto"));
+        l.remove(1);
+
+        type L<F> = IList<(UniqueIntIndex, MapIndex), Person, F>;
+        let check = |l: &L<_>, copy: &L<_>| {
+            assert_eq!(l.to_vec(), copy.to_vec());
+            for p in l.iter() {
+                assert_eq!(Some(p), copy.idx_by(|(ids, _)| ids).get(&p.id).next());
+                assert_eq!(Some(p), copy.idx_by(|(_, n)| n).get(&p.name).next());
+            }
+            for id in [0, -2, 2, 3, 6, 7, 9] {
+                assert_eq!(
+                    l.idx_by(|(ids, _)| ids).contains(&id),
+                    copy.idx_by(|(ids, _)| ids).contains(&id)
+                );
+            }
+        };
+
+        let mut copy = new_list(replay_mode);
+        copy.replay(&log.lock().unwrap());
+        check(&l, &copy);
+
+        // the log is written in a file
+        let path = std::env::temp_dir().join(format!("ff_oplog_{mode:?}_{replay_mode:?}.log"));
+        let file = std::fs::File::create(&path).unwrap();
+        log.lock().unwrap().write_to(file).unwrap();
+
+        // truncate the file with a checkpoint and append the new changes
+        log.lock()
+            .unwrap()
+            .checkpoint(l.iter().cloned().enumerate());
+        let file = std::fs::File::create(&path).unwrap();
+        log.lock().unwrap().write_to(file).unwrap();
+
+        l.push(Person::new(8, "Jo\tanna\n"));
+        let file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        let last = log.lock().unwrap().len() - 1;
+        log.lock().unwrap().write_from(last, file).unwrap();
+
+        let read =
+            OpLog::read_from(std::io::BufReader::new(std::fs::File::open(&path).unwrap())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut copy = new_list(replay_mode);
+        copy.replay(&read);
+        check(&l, &copy);
+        assert_eq!(Some(&Person::new(8, "Jo\tanna\n")), copy.last());
+    }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::shift(RemoveMode::Shift)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn transaction(#[case] mode: RemoveMode) {
+        let mut l = IList::<(UniqueIntIndex, MapIndex), _, _>::from_vec(
+            (|p: &Person| p.id, |p: &Person| p.name.clone()),
+            vec![
+                Person::new(0, "Paul"),
+                Person::new(-2, "Mario"),
+                Person::new(2, "Jasmin"),
+                Person::new(3, "Inge"),
+            ],
+        )
+        .with_remove_mode(mode);
+
+        // swap the unique ids of Paul and Inge, remove Mario and Jasmin
+        let mut t = l.transaction();
+        assert!(t.update(0, |p| p.id = 3));
+        assert!(t.update(3, |p| p.id = 0));
+        assert!(t.update(2, |p| p.id = 5));
+        assert!(t.remove(2));
+        assert!(!t.update(2, |p| p.id = 6));
+        assert!(t.remove(1));
+        assert!(!t.remove(1));
+        assert!(!t.remove(9));
+        t.push(Person::new(-2, "Egon"));
+        assert_eq!(5, t.len());
+        assert_eq!(Ok(()), t.commit());
+
+        assert_eq!(3, l.len());
+        if mode != RemoveMode::Swap {
+            let names = l.iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+            assert_eq!(vec!["Paul", "Inge", "Egon"], names);
+        }
+        let tombstones = if mode == RemoveMode::Tombstone { 2 } else { 0 };
+        assert_eq!(tombstones, l.tombstones());
+
+        let ids = l.idx_by(|(ids, _)| ids);
+        assert_eq!(Some(&Person::new(3, "Paul")), ids.get(&3).next());
+        assert_eq!(Some(&Person::new(0, "Inge")), ids.get(&0).next());
+        assert_eq!(Some(&Person::new(-2, "Egon")), ids.get(&-2).next());
+        assert!(!ids.contains(&2));
+        assert!(!ids.contains(&5));
+
+        let names = l.idx_by(|(_, names)| names);
+        assert!(!names.contains(&"Mario".into()));
+        assert!(!names.contains(&"Jasmin".into()));
+        assert_eq!(
+            Some(&Person::new(0, "Inge")),
+            names.get(&"Inge".into()).next()
+        );
+    }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn transaction_rollback(#[case] mode: RemoveMode) {
+        let mut l = IList::<(UniqueIntIndex, MapIndex), _, _>::from_vec(
+            (|p: &Person| p.id, |p: &Person| p.name.clone()),
+            vec![Person::new(0, "Paul"), Person::new(-2, "Mario")],
+        )
+        .with_remove_mode(mode);
+
+        // the ids in the batch are not unique
+        let mut t = l.transaction();
+        t.remove(1);
+        t.push(Person::new(5, "Egon"));
+        t.push(Person::new(5, "Inge"));
+        assert_eq!(Err(TransactionError::NotUnique), t.commit());
+
+        // the id exists in the List
+        let mut t = l.transaction();
+        t.update(1, |p| p.id = 0);
+        assert_eq!(Err(TransactionError::NotUnique), t.commit());
+
+        let mut t = l.transaction();
+        t.remove(0);
+        t.rollback();
+
+        assert_eq!(&[Person::new(0, "Paul"), Person::new(-2, "Mario")], &l[..]);
+        let ids = l.idx_by(|(ids, _)| ids);
+        assert_eq!(Some(&Person::new(0, "Paul")), ids.get(&0).next());
+        assert_eq!(Some(&Person::new(-2, "Mario")), ids.get(&-2).next());
+        assert!(!ids.contains(&5));
+        assert!(!l.idx_by(|(_, names)| names).contains(&"Egon".into()));
+    }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::shift(RemoveMode::Shift)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn transaction_unique_second_store(#[case] mode: RemoveMode) {
+        let mut l = IList::<(MapIndex, UniqueIntIndex), _, _>::from_vec(
+            (|p: &Person| p.name.clone(), |p: &Person| p.id),
+            vec![Person::new(0, "Paul"), Person::new(-2, "Mario")],
+        )
+        .with_remove_mode(mode);
+
+        // the id exists in the second (unique) Store
+        let mut t = l.transaction();
+        t.update(0, |p| p.name = "Mario".into());
+        t.push(Person::new(3, "Egon"));
+        t.push(Person::new(-2, "Inge"));
+        assert_eq!(Err(TransactionError::NotUnique), t.commit());
+        assert_eq!(&[Person::new(0, "Paul"), Person::new(-2, "Mario")], &l[..]);
+        assert!(!l.idx_by(|(_, ids)| ids).contains(&3));
+
+        // the primary Store is a multi Store, the id -2 is free, after the remove of Mario
+        let mut t = l.transaction();
+        t.push(Person::new(-2, "Paul"));
+        t.remove(1);
+        assert_eq!(Ok(()), t.commit());
+        assert_eq!(2, l.idx_by(|(names, _)| names).get(&"Paul".into()).count());
+        assert_eq!(
+            Some(&Person::new(-2, "Paul")),
+            l.idx_by(|(_, ids)| ids).get(&-2).next()
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the primary Store must be unique")]
+    fn upsert_with_multi_primary() {
+        let mut l = IList::<MapIndex, _, _>::from_vec(
+            |p: &Person| p.name.clone(),
+            vec![Person::new(0, "Paul"), Person::new(1, "Paul")],
+        );
+        l.upsert(Person::new(2, "Paul"));
+    }
+
+    #[test]
+    fn upsert_with_tombstones() {
+        let mut l = IList::<(UniqueIntIndex, MapIndex), _, _>::from_vec(
+            (|p: &Person| p.id, |p: &Person| p.name.clone()),
+            vec![
+                Person::new(0, "Paul"),
+                Person::new(-2, "Mario"),
+                Person::new(2, "Jasmin"),
+            ],
+        )
+        .with_remove_mode(RemoveMode::Tombstone);
+        l.remove(0);
+
+        // the position is the position in the List, not in the Store
+        assert_eq!(Upsert::Updated(1), l.upsert(Person::new(2, "Inge")));
+        assert_eq!(Upsert::Inserted(2), l.upsert(Person::new(0, "Paul")));
+        assert_eq!(
+            Upsert::Updated(0),
+            l.upsert_with(&-2, || unreachable!(), |p| p.name = "Egon".into())
+        );
+        assert_eq!(
+            Upsert::Inserted(3),
+            l.upsert_with(&5, || Person::new(5, "Peter"), |_| unreachable!())
+        );
+
+        let names = l.idx_by(|(_, names)| names);
+        assert!(!names.contains(&"Jasmin".into()));
+        assert!(!names.contains(&"Mario".into()));
+        assert_eq!(
+            Some(&Person::new(2, "Inge")),
+            names.get(&"Inge".into()).next()
+        );
+        assert_eq!(
+            Some(&Person::new(-2, "Egon")),
+            names.get(&"Egon".into()).next()
+        );
+        assert_eq!(
+            Some(&Person::new(5, "Peter")),
+            l.idx_by(|(ids, _)| ids).get(&5).next()
+        );
+        assert_eq!(4, l.len());
+    }
+
+    #[test]
+    fn mutable_guards() {
+        let mut l = IList::<(MultiIntIndex, MapIndex), _, _>::from_vec(
+            (|p: &Person| p.id, |p: &Person| p.name.clone()),
+            vec![
+                Person::new(1, "Paul"),
+                Person::new(2, "Mario"),
+                Person::new(1, "Jasmin"),
+                Person::new(3, "Inge"),
+            ],
+        )
+        .with_remove_mode(RemoveMode::Tombstone);
+        l.remove(0);
+
+        if let Some(mut p) = l.get_mut_by(|(_, names)| names, &"Jasmin".into()) {
+            assert_eq!(1, p.position());
+            p.id = 2;
+        }
+        assert!(!l.idx_by(|(ids, _)| ids).contains(&1));
+
+        // a position in the List (not in the Store, with tombstones)
+        if let Some(mut p) = l.get_mut_at(0) {
+            p.name = "Egon".into();
+        }
+        assert_eq!(
+            Some(&Person::new(2, "Egon")),
+            l.idx_by(|(_, names)| names).get(&"Egon".into()).next()
+        );
+        assert!(!l.idx_by(|(_, names)| names).contains(&"Mario".into()));
+        assert_eq!(
+            vec![&Person::new(2, "Egon"), &Person::new(2, "Jasmin")],
+            l.idx_by(|(ids, _)| ids).get(&2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn filter_mut() {
+        let mut l = IList::<MultiIntIndex, _, _>::from_vec(
+            |p: &Person| p.id,
+            vec![
+                Person::new(1, "Paul"),
+                Person::new(2, "Mario"),
+                Person::new(1, "Jasmin"),
+                Person::new(3, "Inge"),
+            ],
+        );
+
+        let mut it = l.filter_mut(|f| f.eq(&1) | f.eq(&3));
+        assert_eq!(3, it.len());
+        while let Some(mut p) = it.next() {
+            p.id *= 10;
+        }
+        assert!(it.is_empty());
+
+        assert!(!l.idx().contains(&1));
+        assert_eq!(
+            vec![&Person::new(10, "Paul"), &Person::new(10, "Jasmin")],
+            l.idx().get(&10).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&Person::new(30, "Inge")), l.idx().get(&30).next());
+
+        // get_mut by Key
+        l.get_mut(&2).unwrap().id = 20;
+        assert_eq!(Some(&Person::new(20, "Mario")), l.idx().get(&20).next());
+        assert!(l.get_mut(&2).is_none());
+    }
 }
@@ -1,16 +1,39 @@
 #![doc(hidden)]
 //! Base-List for indexed read-write lists.
 //!
-use std::{fmt::Debug, ops::Deref};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    ops::{Bound, Deref, DerefMut, RangeBounds},
+};
 
+#[cfg(feature = "serde")]
+use crate::index::store::Validate;
+#[cfg(feature = "rayon")]
+use crate::index::{par, store::Merge};
 use crate::{
-    collections::{rw::Editable, Retriever},
-    index::store::Store,
+    collections::{
+        rw::{
+            entry::Entry,
+            observer::{Event, ObserverId, Observers},
+            oplog::{Op, OpLog},
+            Editable, RemoveMode, Upsert,
+        },
+        Retriever,
+    },
+    index::{
+        indices::Indices,
+        store::{Filterable, Indexer, Store, Unique},
+        Filter, Indexable,
+    },
 };
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Is a Wrapper for an [`Vec`], which has trigger functions for insert and remove operations
 #[repr(transparent)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TriggerList<I>(Vec<I>);
 
 impl<I> TriggerList<I> {
@@ -92,6 +115,89 @@ impl<I> Default for TriggerList<I> {
     }
 }
 
+/// `ItemList` are the `Items` of a [`List`] with the tombstones (removed positions),
+/// which are not yet compacted (see: [`crate::collections::rw::RemoveMode::Tombstone`]).
+///
+/// The `Stores` contains the (logical) positions, which contains the tombstones.
+/// The `Items` are saved without the tombstones, so the position in the `Vec` (physical)
+/// is the logical position minus the number of tombstones before.
+#[derive(Debug, Clone)]
+pub struct ItemList<I> {
+    items: TriggerList<I>,
+    tombstones: Vec<usize>,
+}
+
+impl<I> ItemList<I> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: TriggerList::with_capacity(capacity),
+            tombstones: Vec::new(),
+        }
+    }
+
+    /// The position in the `Stores` for the given position in the `Vec`.
+    fn to_logical(&self, pos: usize) -> Option<usize> {
+        if pos >= self.items.len() {
+            return None;
+        }
+
+        // the number of tombstones before: `tombstone - index` is ascending (the tombstones are sorted and unique)
+        let (mut lo, mut hi) = (0, self.tombstones.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.tombstones[mid] - mid <= pos {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        Some(pos + lo)
+    }
+
+    /// Iterate over all `Items` with the position in the `Stores` (logical).
+    pub(crate) fn iter_indexed(&self) -> impl Iterator<Item = (usize, &I)> {
+        let mut before = 0;
+        self.items.iter().enumerate().map(move |(pos, item)| {
+            while self
+                .tombstones
+                .get(before)
+                .is_some_and(|t| *t <= pos + before)
+            {
+                before += 1;
+            }
+            (pos + before, item)
+        })
+    }
+
+    /// The position in the `Vec` for the given position in the `Stores`.
+    fn to_physical(&self, logical: usize) -> Option<usize> {
+        let before = self.tombstones.partition_point(|t| *t < logical);
+        if self.tombstones.get(before) == Some(&logical) {
+            return None;
+        }
+        Some(logical - before).filter(|pos| *pos < self.items.len())
+    }
+}
+
+impl<I> Indexable<usize> for ItemList<I> {
+    type Output = I;
+
+    fn item(&self, idx: &usize) -> &Self::Output {
+        if self.tombstones.is_empty() {
+            return &self.items[*idx];
+        }
+        &self.items[*idx - self.tombstones.partition_point(|t| t < idx)]
+    }
+}
+
+impl<I> Deref for ItemList<I> {
+    type Target = Vec<I>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
 ///
 /// `List` is a list with one `Store`.
 /// This means, one `Index`.
@@ -100,19 +206,22 @@ impl<I> Default for TriggerList<I> {
 pub struct List<S, I, F> {
     field: F,
     store: S,
-    items: TriggerList<I>,
+    items: ItemList<I>,
+    mode: RemoveMode,
+    observers: Observers<I, usize>,
 }
 
 impl<S, I, F> List<S, I, F>
 where
-    S: Store<Index = usize>,
-    F: Fn(&I) -> S::Key,
+    S: Indexer<I, F, Index = usize>,
 {
     pub fn new(field: F) -> Self {
         Self {
             field,
             store: S::with_capacity(0),
-            items: TriggerList::with_capacity(0),
+            items: ItemList::with_capacity(0),
+            mode: RemoveMode::default(),
+            observers: Observers::default(),
         }
     }
 
@@ -122,6 +231,26 @@ where
         Self::from_iter(field, v.into_iter())
     }
 
+    /// Create a `List`, the `Stores` are created in parallel (see: [`crate::index::par`]).
+    #[cfg(feature = "rayon")]
+    pub fn par_from_vec(field: F, v: Vec<I>) -> Self
+    where
+        S: Merge + Send,
+        I: Sync,
+        F: Sync,
+    {
+        Self {
+            store: par::to_indexer(&v, &field),
+            field,
+            items: ItemList {
+                items: TriggerList(v),
+                tombstones: Vec::new(),
+            },
+            mode: RemoveMode::default(),
+            observers: Observers::default(),
+        }
+    }
+
     pub fn from_iter<It>(field: F, iter: It) -> Self
     where
         It: IntoIterator<Item = I> + ExactSizeIterator,
@@ -129,7 +258,9 @@ where
         let mut s = Self {
             field,
             store: S::with_capacity(iter.len()),
-            items: TriggerList::with_capacity(iter.len()),
+            items: ItemList::with_capacity(iter.len()),
+            mode: RemoveMode::default(),
+            observers: Observers::default(),
         };
 
         iter.into_iter().for_each(|item| {
@@ -139,16 +270,540 @@ where
         s
     }
 
-    /// Append a new `Item` to the List.
+    /// Append a new `Item` to the List and returns the position in the List.
     pub fn push(&mut self, item: I) -> usize {
-        self.items.push(item, |i, idx| {
-            self.store.insert((self.field)(i), idx);
+        let tombstones = self.items.tombstones.len();
+        let pos = self.items.items.push(item, |i, idx| {
+            self.store.insert_item(&self.field, i, idx + tombstones);
+        });
+        self.observers.notify(Event::Inserted {
+            index: pos,
+            item: &self.items.items[pos],
+        });
+        pos
+    }
+
+    /// Register an observer, which is called with an [`Event`] after every change of an `Item`.
+    pub fn subscribe<O>(&mut self, observer: O) -> ObserverId
+    where
+        O: FnMut(&Event<'_, I, usize>) + Send + Sync + 'static,
+        I: Clone,
+    {
+        self.observers.subscribe(observer)
+    }
+
+    /// Remove the observer with the given id, returns `false`, if the observer doesn't exist.
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        self.observers.unsubscribe(id)
+    }
+
+    /// Insert the `Item`, if the `Key` of the primary `Store` is new,
+    /// otherwise the `Item` with this `Key` is replaced.
+    pub fn upsert(&mut self, item: I) -> Upsert {
+        let key = S::primary_key(&self.field, &item);
+        // only one of the functions (insert or update) is called
+        let item = Cell::new(Some(item));
+        self.upsert_with(
+            &key,
+            || item.take().expect("item for insert"),
+            |it| *it = item.take().expect("item for update"),
+        )
+    }
+
+    /// Call `insert`, if the `Key` of the primary `Store` is new, otherwise call `update` with the `Item` for this `Key`.
+    pub fn upsert_with<N, U>(&mut self, key: &S::PrimaryKey, insert: N, update: U) -> Upsert
+    where
+        N: FnOnce() -> I,
+        U: FnMut(&mut I),
+    {
+        match self.primary_index(key) {
+            Some(logical) => {
+                let pos = self
+                    .items
+                    .to_physical(logical)
+                    .expect("the Store contains only valid positions");
+                self.update_index(logical, update);
+                Upsert::Updated(pos)
+            }
+            None => Upsert::Inserted(self.push(insert())),
+        }
+    }
+
+    /// Update the `Item` with the given `Key` of the primary `Store`.
+    pub fn update_by_primary<U>(&mut self, key: &S::PrimaryKey, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        let logical = self.primary_index(key)?;
+        self.update_index(logical, update)
+    }
+
+    /// Remove the `Item` with the given `Key` of the primary `Store`.
+    pub fn remove_by_primary(&mut self, key: &S::PrimaryKey) -> Option<I> {
+        let logical = self.primary_index(key)?;
+        self.remove_index(logical)
+    }
+
+    /// The `Index` for the given `Key` of the primary `Store`, which must be a unique `Store`.
+    fn primary_index(&self, key: &S::PrimaryKey) -> Option<usize> {
+        let idxs = self.store.primary(key);
+        debug_assert!(
+            idxs.len() <= 1,
+            "the primary Store must be unique, but the Key has {} Items",
+            idxs.len()
+        );
+        idxs.first().copied()
+    }
+
+    /// Iterate over all `Items` with the `Index` (the position in the `Stores`).
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &I)> {
+        self.items.iter_indexed()
+    }
+
+    /// Returns the `Key` of the `Item` for the primary `Store`.
+    pub fn primary_key(&self, item: &I) -> S::PrimaryKey {
+        S::primary_key(&self.field, item)
+    }
+
+    /// Checks the unique `Stores` for the updated (on the given position) and the pushed `Items`.
+    /// `unchanged` returns `true`, if the `Item` on the given position is neither updated nor removed.
+    pub(crate) fn check_unique<'i, U>(
+        &self,
+        updates: &'i BTreeMap<usize, I>,
+        pushes: &'i [I],
+        unchanged: U,
+    ) -> bool
+    where
+        S: Unique<I, F>,
+        U: Fn(usize) -> bool,
+    {
+        let next = self.items.items.len() + self.items.tombstones.len();
+        let changed = updates
+            .iter()
+            .map(|(pos, item)| {
+                (
+                    self.items.to_logical(*pos).expect("valid update position"),
+                    item,
+                )
+            })
+            .chain(pushes.iter().enumerate().map(|(i, item)| (next + i, item)));
+        self.store.check_unique(&self.field, changed, |logical| {
+            self.items.to_physical(*logical).is_some_and(&unchanged)
         })
     }
 
-    pub fn idx(&self) -> Retriever<'_, S, Vec<I>> {
+    /// Update the item on the given position in the List.
+    pub fn update_at<U>(&mut self, pos: usize, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        let logical = self.items.to_logical(pos)?;
+        self.update_index(logical, update)
+    }
+
+    /// Returns a mutable reference (a guard) to the `Item` on the given position in the List.
+    /// All `Stores` are updated, when the guard is dropped.
+    pub fn get_mut_at(&mut self, pos: usize) -> Option<ItemMut<'_, S, I, F>> {
+        let logical = self.items.to_logical(pos)?;
+        let item = self.items.items.get(pos)?;
+        let keys = S::keys(&self.field, item);
+        let old = self.observers.snapshot(item);
+        Some(ItemMut {
+            list: self,
+            logical,
+            pos,
+            keys: Some(keys),
+            old,
+        })
+    }
+
+    /// Returns a mutable reference (a guard) to the first `Item` with the given `Key` of the selected `Store`.
+    /// All `Stores` are updated, when the guard is dropped.
+    pub fn get_mut_by<R, Select>(
+        &mut self,
+        select: Select,
+        key: &R::Key,
+    ) -> Option<ItemMut<'_, S, I, F>>
+    where
+        R: Filterable<Index = usize>,
+        Select: FnOnce(&S) -> &R,
+    {
+        let logical = *select(&self.store).get(key).first()?;
+        let pos = self.items.to_physical(logical)?;
+        self.get_mut_at(pos)
+    }
+
+    /// Returns an [`IterMut`] over all `Items` of the filter result.
+    pub fn filter_mut<P>(&mut self, predicate: P) -> IterMut<'_, S, I, F>
+    where
+        S: Filterable<Index = usize>,
+        P: for<'x> Fn(&Filter<'x, S, ItemList<I>>) -> Indices<'x, usize>,
+    {
+        let positions = predicate(&Filter::new(&self.store, &self.items))
+            .as_slice()
+            .iter()
+            .filter_map(|logical| self.items.to_physical(*logical))
+            .collect::<Vec<_>>();
+
+        IterMut {
+            list: self,
+            positions: positions.into_iter(),
+        }
+    }
+
+    /// Get the [`Entry`] for the given `Key` of the selected `Store`.
+    pub fn entry<R, Select>(&mut self, select: Select, key: &R::Key) -> Entry<'_, S, I, F>
+    where
+        R: Filterable<Index = usize>,
+        Select: FnOnce(&S) -> &R,
+    {
+        let pos = select(&self.store)
+            .get(key)
+            .first()
+            .and_then(|logical| self.items.to_physical(*logical));
+        Entry::new(self, pos)
+    }
+
+    /// Remove the item on the given position in the List.
+    pub fn remove_at(&mut self, pos: usize) -> Option<I> {
+        let logical = self.items.to_logical(pos)?;
+        self.remove_index(logical)
+    }
+
+    /// Returns the position in the List for the given `Index` (the position in the `Store`),
+    /// `None`, if the `Index` is removed (a tombstone) or doesn't exist.
+    pub fn position(&self, index: usize) -> Option<usize> {
+        self.items.to_physical(index)
+    }
+
+    pub fn remove_mode(&self) -> RemoveMode {
+        self.mode
+    }
+
+    /// Set the [`RemoveMode`], existing tombstones are compacted.
+    pub fn set_remove_mode(&mut self, mode: RemoveMode) {
+        self.compact();
+        self.mode = mode;
+    }
+
+    /// The number of tombstones (removed positions), which are not compacted.
+    pub fn tombstones(&self) -> usize {
+        self.items.tombstones.len()
+    }
+
+    /// Remove all tombstones and rewrite the positions in the `Store`.
+    pub fn compact(&mut self) {
+        let tombstones = std::mem::take(&mut self.items.tombstones);
+        if tombstones.is_empty() {
+            return;
+        }
+
+        let mut before = 0;
+        for (pos, item) in self.items.items.iter().enumerate() {
+            while before < tombstones.len() && tombstones[before] <= pos + before {
+                before += 1;
+            }
+
+            if before > 0 {
+                self.store.delete_item(&self.field, item, &(pos + before));
+                self.store.insert_item(&self.field, item, pos);
+            }
+        }
+    }
+
+    /// Append all `Items` to the List.
+    pub fn extend<It>(&mut self, iter: It)
+    where
+        It: IntoIterator<Item = I>,
+    {
+        let iter = iter.into_iter();
+        self.items.items.0.reserve(iter.size_hint().0);
+        iter.for_each(|item| {
+            self.push(item);
+        });
+    }
+
+    /// Insert the `Item` on the given position, all `Items` after are shifted to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos > len`.
+    pub fn insert(&mut self, pos: usize, item: I) {
+        assert!(pos <= self.items.len(), "insert position out of bounds");
+        if pos == self.items.len() {
+            self.push(item);
+            return;
+        }
+
+        // only the positions of the shifted Items are rewritten
+        self.compact();
+        for p in (pos..self.items.len()).rev() {
+            let it = &self.items.items[p];
+            self.store.delete_item(&self.field, it, &p);
+            self.store.insert_item(&self.field, it, p + 1);
+        }
+        self.store.insert_item(&self.field, &item, pos);
+        self.items.items.0.insert(pos, item);
+        self.observers.notify(Event::Inserted {
+            index: pos,
+            item: &self.items.items[pos],
+        });
+    }
+
+    /// Retains only the `Items` specified by the predicate, the order is preserved.
+    /// The removed positions are compacted once.
+    pub fn retain<P>(&mut self, mut predicate: P)
+    where
+        P: FnMut(&I) -> bool,
+    {
+        self.compact();
+
+        let mut pos = 0;
+        let mut tombstones = Vec::new();
+        self.items.items.0.retain(|item| {
+            let keep = predicate(item);
+            if keep {
+                pos += 1;
+            } else {
+                let logical = pos + tombstones.len();
+                self.store.delete_item(&self.field, item, &logical);
+                self.observers.notify(Event::Removed { index: pos, item });
+                tombstones.push(logical);
+            }
+            keep
+        });
+        self.items.tombstones = tombstones;
+        self.compact();
+    }
+
+    /// Removes the `Items` in the given range and returns the removed `Items`, the order is preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds (like [`std::vec::Vec::drain`]).
+    pub fn drain<R>(&mut self, range: R) -> Vec<I>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.compact();
+
+        let start = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let drained = self.items.items.0.drain(range).collect::<Vec<_>>();
+        for (p, item) in drained.iter().enumerate().rev() {
+            self.observers.notify(Event::Removed {
+                index: start + p,
+                item,
+            });
+        }
+
+        for (p, it) in drained.iter().enumerate() {
+            self.store.delete_item(&self.field, it, &(start + p));
+        }
+        if start < self.items.len() {
+            self.items.tombstones = (start..start + drained.len()).collect();
+            self.compact();
+        }
+
+        drained
+    }
+
+    /// Shortens the List to the given `len`, if `len` is greater or equal the current len, nothing happens.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.items.len() {
+            self.drain(len..);
+        }
+    }
+
+    /// Removes all `Items` and all `Keys` from the `Store`.
+    pub fn clear(&mut self) {
+        for (pos, item) in self.items.items.iter().enumerate().rev() {
+            self.observers.notify(Event::Removed { index: pos, item });
+        }
+        self.items.items.0.clear();
+        self.items.tombstones.clear();
+        self.store = S::with_capacity(0);
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, ItemList<I>>
+    where
+        S: Filterable,
+    {
         Retriever::new(&self.store, &self.items)
     }
+
+    /// Returns the [`Retriever`] for the selected `Store`, if the `List` has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, ItemList<I>>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        Retriever::new(select(&self.store), &self.items)
+    }
+
+    /// Apply all [`Op`]s of the given log, independent of the [`RemoveMode`] of this List.
+    /// The same `Items` and `Key` lookups are rebuilt, but not the tombstones (the `Indices` of the `Stores`).
+    pub fn replay(&mut self, log: &OpLog<I>)
+    where
+        I: Clone,
+    {
+        let mut ops = log.iter().peekable();
+        while let Some(op) = ops.next() {
+            match op {
+                Op::Insert { index, item } if *index >= self.items.len() => {
+                    self.push(item.clone());
+                }
+                Op::Insert { index, item } => self.insert(*index, item.clone()),
+                Op::Update { index, item } => {
+                    self.update_at(*index, |i| *i = item.clone());
+                }
+                Op::Remove { index } => {
+                    // a Remove with a following Move is a swap-remove
+                    let mode = if ops.next_if(|op| matches!(op, Op::Move { .. })).is_some() {
+                        self.compact();
+                        RemoveMode::Swap
+                    } else if self.mode == RemoveMode::Swap {
+                        RemoveMode::Shift
+                    } else {
+                        self.mode
+                    };
+                    if let Some(logical) = self.items.to_logical(*index) {
+                        self.remove_logical_with(logical, mode);
+                    }
+                }
+                Op::Move { .. } => {}
+            }
+        }
+    }
+
+    /// Apply the changes of a [`crate::collections::rw::transaction::Transaction`].
+    /// The positions of `updates` and `removes` are the positions before the changes.
+    pub(crate) fn apply_batch(
+        &mut self,
+        mut updates: BTreeMap<usize, I>,
+        removes: BTreeSet<usize>,
+        pushes: Vec<I>,
+    ) {
+        let swap = self.mode == RemoveMode::Swap;
+        let removes = removes.into_iter().collect::<Vec<_>>();
+
+        // remove from the end: by Swap, only the update of the last Item is moved,
+        // otherwise the removed positions are tombstones, which are compacted once (by Shift)
+        for pos in removes.iter().rev() {
+            let last = self.items.items.len() - 1;
+            let logical = self.items.to_logical(*pos).expect("valid remove position");
+            if swap {
+                self.remove_logical_with(logical, RemoveMode::Swap);
+                if let Some(item) = updates.remove(&last) {
+                    updates.insert(*pos, item);
+                }
+            } else {
+                self.remove_logical_with(logical, RemoveMode::Tombstone);
+            }
+        }
+        if self.mode == RemoveMode::Shift {
+            self.compact();
+        }
+
+        let updates = updates
+            .into_iter()
+            .map(|(pos, item)| match swap {
+                true => (pos, item),
+                false => (pos - removes.partition_point(|r| *r < pos), item),
+            })
+            .collect::<Vec<_>>();
+
+        // first delete all old Keys, so a unique Key can move between the updated Items
+        let logicals = updates
+            .iter()
+            .map(|(pos, _)| {
+                let logical = self.items.to_logical(*pos).expect("valid update position");
+                self.store
+                    .delete_item(&self.field, &self.items.items[*pos], &logical);
+                logical
+            })
+            .collect::<Vec<_>>();
+        for ((pos, item), logical) in updates.into_iter().zip(logicals) {
+            self.store.insert_item(&self.field, &item, logical);
+            let old = std::mem::replace(&mut self.items.items.0[pos], item);
+            self.observers.notify(Event::Updated {
+                index: pos,
+                old: &old,
+                new: &self.items.items[pos],
+            });
+        }
+
+        pushes.into_iter().for_each(|item| {
+            self.push(item);
+        });
+    }
+
+    /// Update the item on the given `Index` (the position in the `Store`).
+    pub fn update_index<U>(&mut self, logical: usize, mut update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        let pos = self.items.to_physical(logical)?;
+        let item = self.items.items.get_mut(pos)?;
+        let keys = S::keys(&self.field, item);
+        let old = self.observers.snapshot(item);
+        update(item);
+        self.store.update_item(&self.field, keys, item, logical);
+        if let Some(old) = old {
+            self.observers.notify(Event::Updated {
+                index: pos,
+                old: &old,
+                new: item,
+            });
+        }
+        Some(&*item)
+    }
+
+    /// Remove the item on the given `Index` (the position in the `Store`).
+    pub fn remove_index(&mut self, logical: usize) -> Option<I> {
+        self.remove_logical_with(logical, self.mode)
+    }
+
+    fn remove_logical_with(&mut self, logical: usize, mode: RemoveMode) -> Option<I> {
+        if mode == RemoveMode::Swap {
+            let last = self.items.items.len().checked_sub(1)?;
+            let item = self.items.items.remove(logical, |ops, i, idx| match ops {
+                StoreOp::Delete => self.store.delete_item(&self.field, i, &idx),
+                StoreOp::Insert => self.store.insert_item(&self.field, i, idx),
+            })?;
+            self.observers.notify(Event::Removed {
+                index: logical,
+                item: &item,
+            });
+            if logical < last {
+                self.observers.notify(Event::Moved {
+                    from: last,
+                    to: logical,
+                });
+            }
+            return Some(item);
+        }
+
+        let pos = self.items.to_physical(logical)?;
+        let item = self.items.items.0.remove(pos);
+        self.store.delete_item(&self.field, &item, &logical);
+        self.observers.notify(Event::Removed {
+            index: pos,
+            item: &item,
+        });
+
+        let t = self.items.tombstones.partition_point(|t| *t < logical);
+        self.items.tombstones.insert(t, logical);
+        if mode == RemoveMode::Shift {
+            // only the positions of the Items after the removed Item are rewritten
+            self.compact();
+        }
+
+        Some(item)
+    }
 }
 
 impl<S, I, F> Editable<I> for List<S, I, F>
@@ -159,28 +814,20 @@ where
     type Key = S::Key;
     type Index = usize;
 
-    /// Update the item on the given position.
-    fn update<U>(&mut self, pos: usize, mut update: U) -> Option<&I>
+    /// Update the item on the given position (in the `Store`).
+    fn update<U>(&mut self, logical: usize, update: U) -> Option<&I>
     where
         U: FnMut(&mut I),
     {
-        self.items.get_mut(pos).map(|item| {
-            let key = (self.field)(item);
-            update(item);
-            self.store.update(key, pos, (self.field)(item));
-            &*item
-        })
+        self.update_index(logical, update)
     }
 
-    /// The Item in the list will be removed.
+    /// The Item on the given position (in the `Store`) will be removed.
     ///
     /// ## Hint:
-    /// The remove is a swap_remove ([`std::vec::Vec::swap_remove`])
-    fn remove(&mut self, pos: usize) -> Option<I> {
-        self.items.remove(pos, |ops, i, idx| match ops {
-            StoreOp::Delete => self.store.delete((self.field)(i), &idx),
-            StoreOp::Insert => self.store.insert((self.field)(i), idx),
-        })
+    /// The remove depends on the [`RemoveMode`], the default is a swap_remove ([`std::vec::Vec::swap_remove`]).
+    fn remove(&mut self, logical: usize) -> Option<I> {
+        self.remove_index(logical)
     }
 
     fn get_indices_by_key(&self, key: &Self::Key) -> &[Self::Index] {
@@ -188,18 +835,257 @@ where
     }
 }
 
+impl<S, I, F> List<S, I, F>
+where
+    S: Clone,
+    I: Clone,
+    F: Clone,
+{
+    /// Returns a copy of the `Items` and the `Stores`, the registered observers are not copied.
+    pub fn clone_without_observers(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            store: self.store.clone(),
+            items: self.items.clone(),
+            mode: self.mode,
+            observers: Observers::default(),
+        }
+    }
+}
+
+impl<S, I, F> Indexable<usize> for List<S, I, F> {
+    type Output = I;
+
+    fn item(&self, idx: &usize) -> &Self::Output {
+        self.items.item(idx)
+    }
+}
+
 impl<S, I, F> Deref for List<S, I, F> {
     type Target = Vec<I>;
 
     fn deref(&self) -> &Self::Target {
-        &self.items.0
+        &self.items.items.0
+    }
+}
+
+/// A mutable reference to an `Item` in a [`List`], which remembers the `Keys` before the change.
+/// All `Stores` are updated with the new `Keys`, when the `ItemMut` is dropped.
+pub struct ItemMut<'a, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    list: &'a mut List<S, I, F>,
+    logical: usize,
+    pos: usize,
+    keys: Option<S::Keys>,
+    old: Option<I>,
+}
+
+impl<S, I, F> ItemMut<'_, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    /// The position of the `Item` in the List.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+/// A lending iterator over mutable references ([`ItemMut`]) to the `Items` of a filter result.
+/// Every [`ItemMut`] updates all `Stores`, when it is dropped.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::{index::MapIndex, collections::rw::IList};
+///
+/// let mut l = IList::<MapIndex<&str>, _, _>::from_vec(|s| *s, vec!["a", "b", "a"]);
+///
+/// let mut it = l.filter_mut(|f| f.eq(&"a"));
+/// while let Some(mut s) = it.next() {
+///     *s = "c";
+/// }
+///
+/// assert!(!l.idx().contains(&"a"));
+/// assert_eq!(vec![&"c", &"c"], l.idx().get(&"c").collect::<Vec<_>>());
+/// ```
+pub struct IterMut<'a, S, I, F> {
+    list: &'a mut List<S, I, F>,
+    positions: std::vec::IntoIter<usize>,
+}
+
+impl<S, I, F> IterMut<'_, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    /// Returns a mutable reference to the next `Item`, the reference must be dropped before the next call.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<ItemMut<'_, S, I, F>> {
+        let pos = self.positions.next()?;
+        self.list.get_mut_at(pos)
+    }
+
+    /// The number of remaining `Items`.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.len() == 0
+    }
+}
+
+impl<S, I, F> Deref for ItemMut<'_, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        &self.list.items.items[self.pos]
+    }
+}
+
+impl<S, I, F> DerefMut for ItemMut<'_, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.list.items.items.0[self.pos]
+    }
+}
+
+impl<S, I, F> Drop for ItemMut<'_, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    fn drop(&mut self) {
+        if let Some(keys) = self.keys.take() {
+            let list = &mut *self.list;
+            let item = &list.items.items[self.pos];
+            list.store
+                .update_item(&list.field, keys, item, self.logical);
+            if let Some(old) = self.old.take() {
+                list.observers.notify(Event::Updated {
+                    index: self.pos,
+                    old: &old,
+                    new: item,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+#[serde(rename = "List")]
+struct ListRef<'a, S, I> {
+    store: &'a S,
+    items: &'a [I],
+    tombstones: &'a [usize],
+    mode: RemoveMode,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "List")]
+struct ListData<S, I> {
+    store: S,
+    items: Vec<I>,
+    tombstones: Vec<usize>,
+    mode: RemoveMode,
+}
+
+/// Serialize the `Store(s)`, the `Items`, the tombstones and the [`RemoveMode`] (without the observers).
+#[cfg(feature = "serde")]
+impl<S, I, F> Serialize for List<S, I, F>
+where
+    S: Serialize,
+    I: Serialize,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        ListRef {
+            store: &self.store,
+            items: &self.items.items,
+            tombstones: &self.items.tombstones,
+            mode: self.mode,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, I, F> List<S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    /// Deserialize a `List`, which is serialized with `serialize`.
+    /// The function(s) for the `Keys` can not be serialized, so they must be given.
+    ///
+    /// The `Stores` are validated:
+    /// - the tombstones are sorted and all positions are in the bounds of the `List`
+    /// - every `Store` contains the `Key` and the position of every `Item` and no other positions
+    ///   (no tombstone positions)
+    pub fn deserialize_with<'de, D>(field: F, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        S: Deserialize<'de> + Validate<I, F>,
+        I: Deserialize<'de>,
+    {
+        let data = ListData::<S, I>::deserialize(deserializer)?;
+        let len = data.items.len() + data.tombstones.len();
+
+        if data.tombstones.windows(2).any(|w| w[0] >= w[1])
+            || data.tombstones.last().is_some_and(|t| *t >= len)
+        {
+            return Err(de::Error::custom(
+                "the tombstones are not sorted or out of bounds",
+            ));
+        }
+        if data.mode != RemoveMode::Tombstone && !data.tombstones.is_empty() {
+            return Err(de::Error::custom(
+                "the tombstones are only valid by the RemoveMode: Tombstone",
+            ));
+        }
+        if !data.store.check_positions(len) {
+            return Err(de::Error::custom(
+                "the store contains a position, which is out of bounds",
+            ));
+        }
+        if !data.store.check_count(data.items.len()) {
+            return Err(de::Error::custom(
+                "the number of positions in the store is not the number of items",
+            ));
+        }
+
+        let l = Self {
+            field,
+            store: data.store,
+            items: ItemList {
+                items: TriggerList(data.items),
+                tombstones: data.tombstones,
+            },
+            mode: data.mode,
+            observers: Observers::default(),
+        };
+
+        for (pos, item) in l.items.iter().enumerate() {
+            let logical = l.items.to_logical(pos).unwrap_or(pos);
+            if !l.store.contains_item(&l.field, item, logical) {
+                return Err(de::Error::custom(format!(
+                    "the item on position {pos} is not in the store"
+                )));
+            }
+        }
+        Ok(l)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::index::MultiIntIndex;
+    use crate::index::{MapIndex, MultiIntIndex};
     use rstest::{fixture, rstest};
 
     impl<T> From<Vec<T>> for TriggerList<T> {
@@ -267,12 +1153,12 @@ mod tests {
 
     #[rstest]
     fn update(mut v: TriggerList<String>) {
-        assert_eq!(Some(&String::from("A")), v.get(0));
+        assert_eq!(Some(&String::from("A")), v.first());
 
         // update: "A" -> "AA" => (1, 2)
         let s = v.get_mut(0).unwrap();
         *s = String::from("AA");
-        assert_eq!(Some(&String::from("AA")), v.get(0));
+        assert_eq!(Some(&String::from("AA")), v.first());
     }
 
     #[rstest]
@@ -302,7 +1188,7 @@ mod tests {
 
         assert_eq!(2, v.len());
         assert!(!v.is_empty());
-        assert_eq!(&String::from("C"), v.get(0).unwrap());
+        assert_eq!(&String::from("C"), v.first().unwrap());
 
         let mut it = v.iter();
         assert_eq!(Some(&"C".into()), it.next());
@@ -342,6 +1228,31 @@ mod tests {
         assert_eq!(None, v.remove(1000, |_, _, _| {}));
     }
 
+    #[rstest]
+    #[case::no_tombstones(vec![], 3)]
+    #[case::first(vec![0], 3)]
+    #[case::first_two(vec![0, 1], 3)]
+    #[case::mid(vec![1, 3], 3)]
+    #[case::last(vec![3, 4], 3)]
+    fn logical_physical(#[case] tombstones: Vec<usize>, #[case] len: usize) {
+        let l = ItemList {
+            items: TriggerList((0..len).collect()),
+            tombstones: tombstones.clone(),
+        };
+
+        let logicals = (0..len + tombstones.len())
+            .filter(|p| !tombstones.contains(p))
+            .collect::<Vec<_>>();
+        for (pos, logical) in logicals.iter().enumerate() {
+            assert_eq!(Some(*logical), l.to_logical(pos));
+            assert_eq!(Some(pos), l.to_physical(*logical));
+        }
+        assert_eq!(None, l.to_logical(len));
+        tombstones
+            .iter()
+            .for_each(|t| assert_eq!(None, l.to_physical(*t)));
+    }
+
     fn check_key_idx<S, I, F>(l: &mut List<S, I, F>)
     where
         S: Store<Index = usize>,
@@ -355,7 +1266,7 @@ mod tests {
 
     #[test]
     fn check_key_idx_intindex() {
-        let v = vec![
+        let v = [
             Person::new(0, "Paul"),
             Person::new(-2, "Mario"),
             Person::new(2, "Jasmin"),
@@ -405,7 +1316,7 @@ mod tests {
 
     #[test]
     fn check_key_with_many_idx_intindex() {
-        let v = vec![
+        let v = [
             Person::new(-2, "Paul"),
             Person::new(-2, "Mario"),
             Person::new(2, "Jasmin"),
@@ -419,4 +1330,138 @@ mod tests {
         l.remove(1);
         check_key_idx(&mut l);
     }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap, [9, 8, 2, 0, 7], [8, 2], 7)]
+    #[case::tombstone(RemoveMode::Tombstone, [8, 0, 2, 7, 9], [2, 7], 0)]
+    fn bulk(
+        #[case] mode: RemoveMode,
+        #[case] retained: [i32; 5],
+        #[case] drained: [i32; 2],
+        #[case] truncated: i32,
+    ) {
+        let persons = (0..6).map(|i| Person::new(i % 3, "P")).collect::<Vec<_>>();
+        let mut l = List::<MultiIntIndex, Person, _>::from_vec(|p| p.id, persons);
+        l.set_remove_mode(mode);
+        l.remove_at(2);
+        check_key_idx_multi(&l);
+
+        l.extend([Person::new(7, "E"), Person::new(1, "E")]);
+        check_key_idx_multi(&l);
+
+        l.insert(1, Person::new(8, "I"));
+        l.insert(l.len(), Person::new(9, "I"));
+        assert_eq!(8, l.items[1].id);
+        check_key_idx_multi(&l);
+
+        l.remove_at(0);
+        l.retain(|p| p.id != 1);
+        assert_eq!(
+            retained.to_vec(),
+            l.iter().map(|p| p.id).collect::<Vec<_>>()
+        );
+        check_key_idx_multi(&l);
+
+        l.remove_at(0);
+        assert_eq!(
+            drained.to_vec(),
+            l.drain(1..3).iter().map(|p| p.id).collect::<Vec<_>>()
+        );
+        check_key_idx_multi(&l);
+
+        l.truncate(1);
+        assert_eq!(vec![truncated], l.iter().map(|p| p.id).collect::<Vec<_>>());
+        assert!(!l.idx().contains(&9));
+        check_key_idx_multi(&l);
+
+        l.clear();
+        assert!(l.is_empty());
+        assert!(!l.idx().contains(&truncated));
+        l.push(Person::new(3, "N"));
+        check_key_idx_multi(&l);
+    }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::shift(RemoveMode::Shift)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn multi_stores(#[case] mode: RemoveMode) {
+        let persons = (0..6)
+            .map(|i| Person::new(i % 3, &format!("P{i}")))
+            .collect::<Vec<_>>();
+        let mut l = List::<(MultiIntIndex, MapIndex), Person, _>::from_vec(
+            (|p: &Person| p.id, |p: &Person| p.name.clone()),
+            persons,
+        );
+        l.set_remove_mode(mode);
+
+        let check = |l: &List<(MultiIntIndex, MapIndex), Person, _>| {
+            check_store(l, &l.store.0, |p| p.id);
+            check_store(l, &l.store.1, |p| p.name.clone());
+        };
+
+        l.remove_at(1);
+        l.update_at(0, |p| {
+            p.id = 7;
+            p.name = "U".into();
+        });
+        check(&l);
+        assert!(!l.idx_by(|(_, names)| names).contains(&"P0".into()));
+        assert!(!l.idx_by(|(_, names)| names).contains(&"P1".into()));
+
+        l.insert(1, Person::new(8, "I"));
+        l.retain(|p| p.id != 2);
+        check(&l);
+
+        l.drain(..1);
+        l.push(Person::new(9, "N"));
+        check(&l);
+        assert_eq!(
+            vec!["I", "P3", "P4", "N"],
+            l.iter().map(|p| p.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&Person::new(9, "N")),
+            l.idx_by(|(ids, _)| ids).get(&9).next()
+        );
+    }
+
+    /// all (logical) positions in the given Store (of a tuple of Stores) are correct
+    fn check_store<S, F, R, K>(l: &List<S, Person, F>, store: &R, key: K)
+    where
+        R: Store<Index = usize>,
+        R::Key: PartialEq,
+        K: Fn(&Person) -> R::Key,
+    {
+        l.items.iter().for_each(|item| {
+            let expected = l
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, i)| key(i) == key(item))
+                .map(|(pos, _)| l.items.to_logical(pos).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(expected, store.get(&key(item)));
+        });
+    }
+
+    /// all (logical) positions in the Store are correct (for a Store with many Indices per Key)
+    fn check_key_idx_multi<S, I, F>(l: &List<S, I, F>)
+    where
+        S: Store<Index = usize>,
+        S::Key: PartialEq,
+        F: Fn(&I) -> S::Key,
+    {
+        l.items.iter().for_each(|item| {
+            let key = (l.field)(item);
+            let expected = l
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, i)| (l.field)(i) == key)
+                .map(|(pos, _)| l.items.to_logical(pos).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(expected, l.store.get(&key));
+        });
+    }
 }
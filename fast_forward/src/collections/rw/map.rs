@@ -0,0 +1,541 @@
+//! read-write `Key-Value` Map, the backing `Map` can be a [`std::collections::HashMap`] (default),
+//! a `hashbrown::HashMap` (with feature `hashbrown`) or a [`std::collections::BTreeMap`] for an ordered iteration.
+//!
+use std::{hash::Hash, ops::Deref};
+
+#[cfg(feature = "hashbrown")]
+use hashbrown::HashMap;
+
+#[cfg(not(feature = "hashbrown"))]
+use std::collections::HashMap;
+
+use crate::{
+    collections::{
+        rw::{
+            map_base::{Map, MapItems},
+            observer::{Event, ObserverId},
+            oplog::OpLog,
+            transaction::MapTransaction,
+            Editor, Insert, InsertMode,
+        },
+        Retriever,
+    },
+    index::store::{Filterable, Indexer, Store},
+};
+
+/// [`IMap`] is a read write indexed `Key-Value Map` which owned the given items.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::{index::MultiIntIndex, collections::rw::{IMap, Insert, InsertMode}};
+///
+/// #[derive(PartialEq, Debug, Clone)]
+/// struct Person {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// impl Person {
+///     fn new(id: i32, name: &str) -> Self {
+///         Self {
+///             id,
+///             name: name.into(),
+///         }
+///     }
+/// }
+///
+/// let mut m = IMap::<MultiIntIndex<i32, &str>, _, _, _>::new(|p: &Person| p.id);
+/// assert_eq!(Insert::Inserted, m.insert("Paul", Person::new(0, "Paul")));
+/// assert_eq!(Insert::Inserted, m.insert("Mario", Person::new(-2, "Mario")));
+///
+/// // the default: an insert with an existing Key is ignored
+/// assert_eq!(Insert::Ignored(Person::new(5, "Paul")), m.insert("Paul", Person::new(5, "Paul")));
+/// assert_eq!(&Person::new(0, "Paul"), m.idx().get(&0).next().unwrap());
+///
+/// m.idx_mut().update_by_key(&-2, |p| { p.id = 99; });
+/// assert_eq!(&Person::new(99, "Mario"), m.idx().get(&99).next().unwrap());
+///
+/// let mut m = m.with_insert_mode(InsertMode::Replace);
+/// assert_eq!(Insert::Replaced(Person::new(0, "Paul")), m.insert("Paul", Person::new(5, "Paul")));
+/// assert!(!m.idx().contains(&0));
+/// assert_eq!(&Person::new(5, "Paul"), m.idx().get(&5).next().unwrap());
+/// ```
+///
+/// With a [`std::collections::BTreeMap`] as backing `Map`, the `Items` are iterated in the order of the `Keys`:
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use fast_forward::{index::MapIndex, collections::rw::IMap};
+///
+/// let mut m = IMap::<MapIndex<&str, u8>, _, _, _, BTreeMap<_, _>>::from_map(
+///     |v: &&str| *v,
+///     BTreeMap::from([(3, "c"), (1, "a")]),
+/// );
+/// m.insert(2, "b");
+/// m.update(3, |v| *v = "a");
+///
+/// assert_eq!(vec![&"a", &"b", &"a"], m.values().collect::<Vec<_>>());
+/// assert_eq!(vec![&"a", &"a"], m.idx().get(&"a").collect::<Vec<_>>());
+/// ```
+#[derive(Debug)]
+pub struct IMap<S, I, X, F, M = HashMap<X, I>> {
+    map: Map<S, I, X, F, M>,
+    mode: InsertMode,
+}
+
+impl<S, I, X, F> IMap<S, I, X, F>
+where
+    S: Indexer<I, F, Index = X>,
+    X: Hash + Eq + Clone,
+{
+    pub fn new(field: F) -> Self {
+        Self {
+            map: Map::new(field),
+            mode: InsertMode::default(),
+        }
+    }
+
+    pub fn from_iter<It>(field: F, iter: It) -> Self
+    where
+        It: IntoIterator<Item = (X, I)> + ExactSizeIterator,
+    {
+        Self {
+            map: Map::from_iter(field, iter),
+            mode: InsertMode::default(),
+        }
+    }
+}
+
+impl<S, I, X, F, M> IMap<S, I, X, F, M>
+where
+    S: Indexer<I, F, Index = X>,
+    X: Clone,
+    M: MapItems<X, I>,
+{
+    /// Create an empty `IMap` with the given `capacity` and the backing `Map` from the type `M`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use fast_forward::{index::MapIndex, collections::rw::IMap};
+    ///
+    /// let mut m = IMap::<MapIndex<&str, u8>, _, _, _, BTreeMap<_, _>>::with_capacity(|v: &&str| *v, 0);
+    /// m.insert(2, "b");
+    /// m.insert(1, "a");
+    ///
+    /// assert_eq!(vec![&"a", &"b"], m.values().collect::<Vec<_>>());
+    /// ```
+    pub fn with_capacity(field: F, capacity: usize) -> Self {
+        Self {
+            map: Map::with_capacity(field, capacity),
+            mode: InsertMode::default(),
+        }
+    }
+
+    /// Create an `IMap` with the given backing `Map` (e.g. [`std::collections::BTreeMap`]),
+    /// all `Items` of the given `Map` are indexed.
+    pub fn from_map(field: F, items: M) -> Self {
+        Self {
+            map: Map::from_map(field, items),
+            mode: InsertMode::default(),
+        }
+    }
+
+    /// Set the [`InsertMode`] for this Map.
+    pub fn with_insert_mode(mut self, mode: InsertMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn insert_mode(&self) -> InsertMode {
+        self.mode
+    }
+
+    /// Insert a new `Item` to the Map.
+    ///
+    /// ## Hint:
+    /// If the `Key` already exist, it depends on the [`InsertMode`]:
+    /// - [`InsertMode::Ignore`]: [`Insert::Ignored`] with the given `Item`, the Map is not changed
+    /// - [`InsertMode::Replace`]: [`Insert::Replaced`] with the replaced `Item`
+    pub fn insert(&mut self, key: X, item: I) -> Insert<I> {
+        match self.mode {
+            InsertMode::Ignore => {
+                if self.map.contains_key(&key) {
+                    Insert::Ignored(item)
+                } else {
+                    self.map.insert(key, item);
+                    Insert::Inserted
+                }
+            }
+            InsertMode::Replace => match self.map.replace(key, item) {
+                Some(old) => Insert::Replaced(old),
+                None => Insert::Inserted,
+            },
+        }
+    }
+
+    /// Update the item on the given `Key`.
+    pub fn update<U>(&mut self, key: X, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.map.update(key, update)
+    }
+
+    /// The Item with the given `Key` will be removed.
+    pub fn remove(&mut self, key: X) -> Option<I> {
+        self.map.remove(key)
+    }
+
+    /// Register an observer, which is called with an [`Event`] after every change of an `Item`.
+    /// A replaced `Item` (see: [`InsertMode::Replace`]) is an [`Event::Updated`].
+    pub fn subscribe<O>(&mut self, observer: O) -> ObserverId
+    where
+        O: FnMut(&Event<'_, I, X>) + Send + Sync + 'static,
+        I: Clone,
+    {
+        self.map.subscribe(observer)
+    }
+
+    /// Remove the observer with the given id, returns `false`, if the observer doesn't exist.
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        self.map.unsubscribe(id)
+    }
+
+    /// Apply all `Ops` of the given [`OpLog`], to rebuild the `Items` and the `Stores`.
+    pub fn replay(&mut self, log: &OpLog<I, X>)
+    where
+        I: Clone,
+    {
+        self.map.replay(log)
+    }
+
+    /// Start a [`MapTransaction`], which stages `insert`, `update` and `remove` operations.
+    /// By `commit` all operations are applied or none (see: [`crate::collections::rw::transaction`]).
+    pub fn transaction(&mut self) -> MapTransaction<'_, S, I, X, F, M>
+    where
+        X: Hash + Eq,
+    {
+        MapTransaction::new(&mut self.map, self.mode)
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, M>
+    where
+        S: Filterable,
+    {
+        self.map.idx()
+    }
+
+    /// Returns the [`Retriever`] for the selected `Store`, if the Map has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, M>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        self.map.idx_by(select)
+    }
+
+    pub fn idx_mut(&mut self) -> Editor<'_, I, Map<S, I, X, F, M>>
+    where
+        S: Store<Index = X>,
+        F: Fn(&I) -> S::Key,
+    {
+        Editor::new(&mut self.map)
+    }
+}
+
+impl<S, I, X, F, M> Deref for IMap<S, I, X, F, M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        collections::rw::transaction::TransactionError,
+        index::{MapIndex, MultiIntIndex, UniqueUIntIndex},
+    };
+    use rstest::rstest;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Car(usize, &'static str);
+
+    #[rstest]
+    #[case::ignore(InsertMode::Ignore, Car(1, "BMW"), Insert::Ignored(Car(1, "Audi")))]
+    #[case::replace(InsertMode::Replace, Car(1, "Audi"), Insert::Replaced(Car(1, "BMW")))]
+    fn insert_existing_key(
+        #[case] mode: InsertMode,
+        #[case] expected: Car,
+        #[case] returned: Insert<Car>,
+    ) {
+        let mut m = IMap::<MapIndex<&str, usize>, _, _, _>::from_iter(
+            |c: &Car| c.1,
+            [(1, Car(1, "BMW")), (2, Car(2, "VW"))].into_iter(),
+        )
+        .with_insert_mode(mode);
+
+        assert_eq!(returned, m.insert(1, Car(1, "Audi")));
+        assert_eq!(2, m.len());
+        assert_eq!(Some(&expected), m.get(&1));
+        assert_eq!(
+            vec![&expected],
+            m.idx().get(&expected.1).collect::<Vec<_>>()
+        );
+
+        let other = if expected.1 == "BMW" { "Audi" } else { "BMW" };
+        assert!(!m.idx().contains(&other));
+    }
+
+    #[test]
+    fn update_remove() {
+        let mut m = IMap::<UniqueUIntIndex<usize, &str>, _, _, _>::new(|c: &Car| c.0);
+        assert_eq!(Insert::Inserted, m.insert("BMW", Car(1, "BMW")));
+        assert_eq!(Insert::Inserted, m.insert("VW", Car(2, "VW")));
+
+        assert_eq!(Some(&Car(5, "BMW")), m.update("BMW", |c| c.0 = 5));
+        assert_eq!(None, m.update("Audi", |c| c.0 = 5));
+        assert!(!m.idx().contains(&1));
+        assert_eq!(Some(&Car(5, "BMW")), m.idx().get(&5).next());
+
+        assert_eq!(Some(Car(2, "VW")), m.remove("VW"));
+        assert_eq!(None, m.remove("VW"));
+        assert!(!m.idx().contains(&2));
+
+        m.idx_mut().remove_by_key(&5);
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn editor_with_string_keys() {
+        let mut m = IMap::<MapIndex<&str, String>, _, _, _>::new(|c: &Car| c.1);
+        m.insert("a".into(), Car(1, "BMW"));
+        m.insert("b".into(), Car(2, "VW"));
+        m.insert("c".into(), Car(3, "BMW"));
+
+        m.idx_mut().update_by_key(&"BMW", |c| c.0 += 10);
+        assert_eq!(Some(&Car(11, "BMW")), m.get("a"));
+
+        let mut removed = Vec::new();
+        m.idx_mut()
+            .remove_by_key_with_cb(&"BMW", |c| removed.push(c.0));
+        removed.sort();
+        assert_eq!(vec![11, 13], removed);
+        assert_eq!(1, m.len());
+    }
+
+    #[test]
+    fn btree_map() {
+        use std::collections::BTreeMap;
+
+        let mut m = IMap::<UniqueUIntIndex<usize, &str>, _, _, _, BTreeMap<_, _>>::from_map(
+            |c: &Car| c.0,
+            BTreeMap::new(),
+        )
+        .with_insert_mode(InsertMode::Replace);
+        m.insert("VW", Car(2, "VW"));
+        m.insert("Audi", Car(3, "Audi"));
+        m.insert("BMW", Car(1, "BMW"));
+        assert_eq!(Insert::Replaced(Car(2, "VW")), m.insert("VW", Car(4, "VW")));
+
+        assert_eq!(
+            vec!["Audi", "BMW", "VW"],
+            m.keys().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&Car(4, "VW")), m.idx().get(&4).next());
+        assert!(!m.idx().contains(&2));
+
+        m.idx_mut().remove_by_key(&3);
+        assert_eq!(vec!["BMW", "VW"], m.keys().copied().collect::<Vec<_>>());
+        assert_eq!(
+            vec![&Car(1, "BMW"), &Car(4, "VW")],
+            m.idx().get_many([1, 4]).collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::ignore(InsertMode::Ignore, Err(TransactionError::KeyExists))]
+    #[case::replace(InsertMode::Replace, Ok(()))]
+    fn transaction_insert_mode(
+        #[case] mode: InsertMode,
+        #[case] expected: Result<(), TransactionError>,
+    ) {
+        let mut m = IMap::<UniqueUIntIndex<usize, &str>, _, _, _>::from_iter(
+            |c: &Car| c.0,
+            [("a", Car(1, "BMW")), ("b", Car(2, "VW"))].into_iter(),
+        )
+        .with_insert_mode(mode);
+
+        let mut t = m.transaction();
+        t.insert("c", Car(3, "Audi"));
+        t.insert("a", Car(4, "Opel"));
+        assert_eq!(expected, t.commit());
+
+        if expected.is_ok() {
+            assert_eq!(Some(&Car(4, "Opel")), m.get("a"));
+            assert!(!m.idx().contains(&1));
+            assert_eq!(Some(&Car(3, "Audi")), m.idx().get(&3).next());
+        } else {
+            assert_eq!(2, m.len());
+            assert_eq!(Some(&Car(1, "BMW")), m.idx().get(&1).next());
+            assert!(!m.idx().contains(&3));
+        }
+    }
+
+    #[test]
+    fn transaction() {
+        let mut m = IMap::<UniqueUIntIndex<usize, &str>, _, _, _>::from_iter(
+            |c: &Car| c.0,
+            [("a", Car(1, "BMW")), ("b", Car(2, "VW"))].into_iter(),
+        );
+
+        // swap the unique ids and remove + insert the same key
+        let mut t = m.transaction();
+        assert!(t.update("a", |c| c.0 = 2));
+        assert!(t.update("b", |c| c.0 = 1));
+        assert!(!t.update("c", |c| c.0 = 3));
+        assert!(t.remove("b"));
+        assert!(!t.remove("b"));
+        t.insert("b", Car(1, "Audi"));
+        assert_eq!(2, t.len());
+        assert_eq!(Ok(()), t.commit());
+
+        assert_eq!(Some(&Car(2, "BMW")), m.idx().get(&2).next());
+        assert_eq!(Some(&Car(1, "Audi")), m.idx().get(&1).next());
+
+        // not unique: rollback
+        let mut t = m.transaction();
+        t.insert("c", Car(2, "Opel"));
+        t.remove("b");
+        assert_eq!(Err(TransactionError::NotUnique), t.commit());
+        assert_eq!(2, m.len());
+        assert!(!m.contains_key("c"));
+        assert_eq!(Some(&Car(1, "Audi")), m.idx().get(&1).next());
+
+        let mut t = m.transaction();
+        t.remove("a");
+        t.rollback();
+        assert_eq!(Some(&Car(2, "BMW")), m.get("a"));
+    }
+
+    #[test]
+    fn transaction_multi_primary() {
+        let mut m = IMap::<MultiIntIndex<i32, &str>, _, _, _>::from_iter(
+            |c: &Car| c.0 as i32,
+            [("a", Car(1, "BMW"))].into_iter(),
+        );
+
+        // a multi primary Store is not a unique constraint
+        let mut t = m.transaction();
+        t.insert("b", Car(1, "VW"));
+        t.insert("c", Car(1, "Audi"));
+        assert_eq!(Ok(()), t.commit());
+        assert_eq!(3, m.len());
+        assert_eq!(3, m.idx().get(&1).count());
+    }
+
+    #[test]
+    fn observer() {
+        use crate::collections::rw::observer::Event;
+        use std::sync::{Arc, Mutex};
+
+        let mut m = IMap::<UniqueUIntIndex<usize, &str>, _, _, _>::new(|c: &Car| c.0)
+            .with_insert_mode(InsertMode::Replace);
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let l = log.clone();
+        let id = m.subscribe(move |e| {
+            l.lock().unwrap().push(match e {
+                Event::Inserted { index, item } => format!("+{index}:{}", item.1),
+                Event::Updated { index, old, new } => format!("~{index}:{}>{}", old.0, new.0),
+                Event::Removed { index, item } => format!("-{index}:{}", item.1),
+                Event::Moved { .. } => unreachable!(),
+            })
+        });
+
+        m.insert("a", Car(1, "BMW"));
+        m.insert("b", Car(2, "VW"));
+        m.insert("a", Car(3, "BMW"));
+        m.update("b", |c| c.0 = 4);
+        m.update("x", |c| c.0 = 4);
+        m.remove("a");
+        m.idx_mut().update_by_key(&4, |c| c.0 = 5);
+        assert_eq!(
+            vec!["+a:BMW", "+b:VW", "~a:1>3", "~b:2>4", "-a:BMW", "~b:4>5"],
+            *log.lock().unwrap()
+        );
+
+        assert!(m.unsubscribe(id));
+        assert!(!m.unsubscribe(id));
+        m.remove("b");
+        assert_eq!(6, log.lock().unwrap().len());
+    }
+
+    #[test]
+    fn oplog_replay() {
+        use crate::collections::rw::oplog::{Op, OpLog};
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(OpLog::new()));
+        let mut m = IMap::<MapIndex<&str, usize>, _, _, _>::new(|c: &Car| c.1)
+            .with_insert_mode(InsertMode::Replace);
+        m.subscribe(OpLog::recorder(&log));
+
+        m.insert(1, Car(1, "BMW"));
+        m.insert(2, Car(2, "VW"));
+        m.insert(1, Car(1, "Audi"));
+        m.update(2, |c| c.1 = "Opel");
+        m.remove(1);
+        assert_eq!(
+            Op::Update {
+                index: 1,
+                item: Car(1, "Audi")
+            },
+            log.lock().unwrap()[2]
+        );
+
+        let mut copy = IMap::<MapIndex<&str, usize>, _, _, _>::new(|c: &Car| c.1);
+        copy.replay(&log.lock().unwrap());
+        assert_eq!(*m, *copy);
+        assert_eq!(Some(&Car(2, "Opel")), copy.idx().get(&"Opel").next());
+        assert!(!copy.idx().contains(&"Audi"));
+        assert!(!copy.idx().contains(&"VW"));
+
+        log.lock()
+            .unwrap()
+            .checkpoint(m.iter().map(|(k, c)| (*k, c.clone())));
+        assert_eq!(1, log.lock().unwrap().len());
+    }
+
+    #[test]
+    fn multi_stores() {
+        let mut m = IMap::<(UniqueUIntIndex<usize, &str>, MapIndex<&str, &str>), _, _, _>::new((
+            |c: &Car| c.0,
+            |c: &Car| c.1,
+        ))
+        .with_insert_mode(InsertMode::Replace);
+        m.insert("a", Car(1, "BMW"));
+        m.insert("b", Car(2, "VW"));
+        m.insert("a", Car(3, "Audi"));
+        m.update("b", |c| c.0 = 5);
+
+        let ids = m.idx_by(|(ids, _)| ids);
+        assert!(!ids.contains(&1));
+        assert!(!ids.contains(&2));
+        assert_eq!(Some(&Car(3, "Audi")), ids.get(&3).next());
+        assert_eq!(Some(&Car(5, "VW")), ids.get(&5).next());
+
+        let names = m.idx_by(|(_, names)| names);
+        assert!(!names.contains(&"BMW"));
+        assert_eq!(Some(&Car(3, "Audi")), names.get(&"Audi").next());
+
+        assert_eq!(Some(Car(5, "VW")), m.remove("b"));
+        assert!(!m.idx_by(|(ids, _)| ids).contains(&5));
+        assert!(!m.idx_by(|(_, names)| names).contains(&"VW"));
+    }
+}
@@ -1,7 +1,7 @@
 #![doc(hidden)]
 //! Base-Map for indexed read-write Maps.
 //!
-use std::{hash::Hash, ops::Deref};
+use std::{collections::BTreeMap, hash::Hash, ops::Deref};
 
 #[cfg(feature = "hashbrown")]
 use hashbrown::HashMap;
@@ -10,22 +10,124 @@ use hashbrown::HashMap;
 use std::collections::HashMap;
 
 use crate::{
-    collections::{rw::Editable, Retriever},
-    index::store::Store,
+    collections::{
+        rw::{
+            observer::{Event, ObserverId, Observers},
+            oplog::{Op, OpLog},
+            Editable,
+        },
+        Retriever,
+    },
+    index::store::{Filterable, Indexer, Store, Unique},
 };
 
-/// Is a Wrapper for an [`std::collections::HashMap`], which has trigger functions for insert and remove operations
+/// `MapItems` describe the operations of the backing `Map` (e.g. `HashMap` or `BTreeMap`) of a [`TriggerMap`].
+pub trait MapItems<X, I> {
+    fn with_capacity(capacity: usize) -> Self;
+
+    fn contains_key(&self, index: &X) -> bool;
+
+    fn get(&self, index: &X) -> Option<&I>;
+
+    fn get_mut(&mut self, index: &X) -> Option<&mut I>;
+
+    fn insert(&mut self, index: X, item: I) -> Option<I>;
+
+    fn remove(&mut self, index: &X) -> Option<I>;
+
+    /// Call the `callback` for every `index` and `Item` in the Map.
+    fn for_each_item<C: FnMut(&X, &I)>(&self, callback: C);
+}
+
+macro_rules! hash_map_items {
+    ( $( $t:ty ),* ) => {
+        $(
+        impl<X, I> MapItems<X, I> for $t
+        where
+            X: Hash + Eq,
+        {
+            fn with_capacity(capacity: usize) -> Self {
+                <$t>::with_capacity(capacity)
+            }
+
+            fn contains_key(&self, index: &X) -> bool {
+                self.contains_key(index)
+            }
+
+            fn get(&self, index: &X) -> Option<&I> {
+                self.get(index)
+            }
+
+            fn get_mut(&mut self, index: &X) -> Option<&mut I> {
+                self.get_mut(index)
+            }
+
+            fn insert(&mut self, index: X, item: I) -> Option<I> {
+                self.insert(index, item)
+            }
+
+            fn remove(&mut self, index: &X) -> Option<I> {
+                self.remove(index)
+            }
+
+            fn for_each_item<C: FnMut(&X, &I)>(&self, mut callback: C) {
+                self.iter().for_each(|(index, item)| callback(index, item))
+            }
+        }
+        )*
+    };
+}
+
+hash_map_items!(std::collections::HashMap<X, I>);
+
+#[cfg(feature = "hashbrown")]
+hash_map_items!(hashbrown::HashMap<X, I>);
+
+impl<X, I> MapItems<X, I> for BTreeMap<X, I>
+where
+    X: Ord,
+{
+    fn with_capacity(_capacity: usize) -> Self {
+        BTreeMap::new()
+    }
+
+    fn contains_key(&self, index: &X) -> bool {
+        self.contains_key(index)
+    }
+
+    fn get(&self, index: &X) -> Option<&I> {
+        self.get(index)
+    }
+
+    fn get_mut(&mut self, index: &X) -> Option<&mut I> {
+        self.get_mut(index)
+    }
+
+    fn insert(&mut self, index: X, item: I) -> Option<I> {
+        self.insert(index, item)
+    }
+
+    fn remove(&mut self, index: &X) -> Option<I> {
+        self.remove(index)
+    }
+
+    fn for_each_item<C: FnMut(&X, &I)>(&self, mut callback: C) {
+        self.iter().for_each(|(index, item)| callback(index, item))
+    }
+}
+
+/// Is a Wrapper for a `Map` (default: [`std::collections::HashMap`]), which has trigger functions for insert and remove operations
 #[repr(transparent)]
 #[derive(Debug)]
-pub struct TriggerMap<I, X>(HashMap<X, I>);
+pub struct TriggerMap<I, X, M = HashMap<X, I>>(M, std::marker::PhantomData<(X, I)>);
 
-impl<I, X> TriggerMap<I, X>
+impl<I, X, M> TriggerMap<I, X, M>
 where
-    X: Hash + Eq,
+    M: MapItems<X, I>,
 {
     /// Create a `Map` with given `capacity`.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(HashMap::with_capacity(capacity))
+        Self(M::with_capacity(capacity), std::marker::PhantomData)
     }
 
     // Return the `Item` from the given index for updating the `Item`.
@@ -42,14 +144,13 @@ where
         X: Clone,
         Trigger: FnMut(X, &I),
     {
-        match self.0.get(&index) {
-            Some(_) => false, // the index already exists, no insert is possible
-            None => {
-                insert(index.clone(), &item);
-                self.0.insert(index, item);
-                true
-            }
+        if self.0.contains_key(&index) {
+            return false; // the index already exists, no insert is possible
         }
+
+        insert(index.clone(), &item);
+        self.0.insert(index, item);
+        true
     }
 
     /// The Item in the Map will be removed.
@@ -64,8 +165,8 @@ where
     }
 }
 
-impl<I, X> Deref for TriggerMap<I, X> {
-    type Target = HashMap<X, I>;
+impl<I, X, M> Deref for TriggerMap<I, X, M> {
+    type Target = M;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -77,24 +178,20 @@ impl<I, X> Deref for TriggerMap<I, X> {
 /// This means, one `Index`.
 ///
 #[derive(Debug)]
-pub struct Map<S, I, X, F> {
+pub struct Map<S, I, X, F, M = HashMap<X, I>> {
     field: F,
     store: S,
-    items: TriggerMap<I, X>,
+    items: TriggerMap<I, X, M>,
+    observers: Observers<I, X>,
 }
 
 impl<S, I, X, F> Map<S, I, X, F>
 where
-    S: Store<Index = X>,
-    F: Fn(&I) -> S::Key,
+    S: Indexer<I, F, Index = X>,
     X: Hash + Eq,
 {
     pub fn new(field: F) -> Self {
-        Self {
-            field,
-            store: S::with_capacity(0),
-            items: TriggerMap::with_capacity(0),
-        }
+        Self::with_capacity(field, 0)
     }
 
     pub fn from_iter<It>(field: F, iter: It) -> Self
@@ -102,11 +199,7 @@ where
         It: IntoIterator<Item = (X, I)> + ExactSizeIterator,
         X: Clone,
     {
-        let mut s = Self {
-            field,
-            store: S::with_capacity(iter.len()),
-            items: TriggerMap::with_capacity(iter.len()),
-        };
+        let mut s = Self::with_capacity(field, iter.len());
 
         iter.into_iter().for_each(|(index, item)| {
             s.insert(index, item);
@@ -114,49 +207,197 @@ where
 
         s
     }
+}
+
+impl<S, I, X, F, M> Map<S, I, X, F, M>
+where
+    S: Indexer<I, F, Index = X>,
+    M: MapItems<X, I>,
+{
+    pub fn with_capacity(field: F, capacity: usize) -> Self {
+        Self {
+            field,
+            store: S::with_capacity(capacity),
+            items: TriggerMap::with_capacity(capacity),
+            observers: Observers::default(),
+        }
+    }
+
+    /// Create a `Map` from the given backing `Map`, all `Items` are indexed.
+    pub fn from_map(field: F, items: M) -> Self
+    where
+        X: Clone,
+    {
+        let mut store = S::with_capacity(0);
+        items.for_each_item(|index, item| store.insert_item(&field, item, index.clone()));
+
+        Self {
+            field,
+            store,
+            items: TriggerMap(items, std::marker::PhantomData),
+            observers: Observers::default(),
+        }
+    }
 
     /// Insert a new `Item` to the Map.
     pub fn insert(&mut self, index: X, item: I) -> bool
     where
         X: Clone,
     {
-        self.items.insert(index, item, |index, item| {
-            self.store.insert((self.field)(item), index);
-        })
+        let inserted = self.items.insert(index.clone(), item, |index, item| {
+            self.store.insert_item(&self.field, item, index);
+        });
+        if inserted && !self.observers.is_empty() {
+            let item = self.items.0.get(&index).expect("inserted item");
+            self.observers.notify(Event::Inserted { index, item });
+        }
+        inserted
+    }
+
+    /// Register an observer, which is called with an [`Event`] after every change of an `Item`.
+    pub fn subscribe<O>(&mut self, observer: O) -> ObserverId
+    where
+        O: FnMut(&Event<'_, I, X>) + Send + Sync + 'static,
+        I: Clone,
+    {
+        self.observers.subscribe(observer)
+    }
+
+    /// Remove the observer with the given id, returns `false`, if the observer doesn't exist.
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        self.observers.unsubscribe(id)
+    }
+
+    /// Insert the `Item` to the Map, an existing `Item` with the same `index` is replaced.
+    pub fn replace(&mut self, index: X, item: I) -> Option<I>
+    where
+        X: Clone,
+    {
+        let old = self.items.remove(&index, |index, old| {
+            self.store.delete_item(&self.field, old, index);
+        });
+        self.items.insert(index.clone(), item, |index, item| {
+            self.store.insert_item(&self.field, item, index);
+        });
+
+        let new = self.items.0.get(&index).expect("inserted item");
+        match &old {
+            Some(old) => self.observers.notify(Event::Updated { index, old, new }),
+            None => self.observers.notify(Event::Inserted { index, item: new }),
+        }
+        old
+    }
+
+    /// Returns the `Key` of the `Item` for the primary `Store`.
+    pub fn primary_key(&self, item: &I) -> S::PrimaryKey {
+        S::primary_key(&self.field, item)
+    }
+
+    /// Returns the `Indices` (the Map keys) for the given `Key` of the primary `Store`.
+    pub fn primary(&self, key: &S::PrimaryKey) -> &[X] {
+        self.store.primary(key)
+    }
+
+    /// Checks the unique `Stores` for the changed `Items` (with the Map keys).
+    /// `unchanged` returns `true`, if the `Item` with the given Map key is neither inserted nor removed.
+    pub(crate) fn check_unique<'i, It, U>(&self, changed: It, unchanged: U) -> bool
+    where
+        S: Unique<I, F>,
+        I: 'i,
+        It: Iterator<Item = (X, &'i I)> + Clone,
+        U: Fn(&X) -> bool,
+    {
+        self.store.check_unique(&self.field, changed, unchanged)
+    }
+
+    /// Apply all [`Op`]s of the given log.
+    pub fn replay(&mut self, log: &OpLog<I, X>)
+    where
+        I: Clone,
+        X: Clone,
+    {
+        for op in log.iter() {
+            match op {
+                Op::Insert { index, item } | Op::Update { index, item } => {
+                    self.replace(index.clone(), item.clone());
+                }
+                Op::Remove { index } => {
+                    self.remove(index.clone());
+                }
+                Op::Move { .. } => {}
+            }
+        }
+    }
+
+    /// Update the item on the given key (index).
+    pub fn update<U>(&mut self, index: X, mut update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+        X: Clone,
+    {
+        let item = self.items.get_mut(&index)?;
+        let keys = S::keys(&self.field, item);
+        let old = self.observers.snapshot(item);
+        update(item);
+        self.store
+            .update_item(&self.field, keys, item, index.clone());
+        if let Some(old) = old {
+            self.observers.notify(Event::Updated {
+                index,
+                old: &old,
+                new: item,
+            });
+        }
+        Some(&*item)
+    }
+
+    /// The Item in the Map will be removed.
+    pub fn remove(&mut self, index: X) -> Option<I> {
+        let item = self.items.remove(&index, |index, item| {
+            self.store.delete_item(&self.field, item, index);
+        })?;
+        self.observers.notify(Event::Removed { index, item: &item });
+        Some(item)
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, M>
+    where
+        S: Filterable,
+    {
+        Retriever::new(&self.store, &self.items.0)
     }
 
-    pub fn idx(&self) -> Retriever<'_, S, HashMap<X, I>> {
-        Retriever::new(&self.store, &self.items)
+    /// Returns the [`Retriever`] for the selected `Store`, if the `Map` has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, M>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        Retriever::new(select(&self.store), &self.items.0)
     }
 }
 
-impl<S, I, X, F> Editable<I> for Map<S, I, X, F>
+impl<S, I, X, F, M> Editable<I> for Map<S, I, X, F, M>
 where
     S: Store<Index = X>,
     F: Fn(&I) -> S::Key,
-    X: Hash + Eq,
+    M: MapItems<X, I>,
+    X: Clone,
 {
     type Key = S::Key;
     type Index = X;
 
     /// Update the item on the given key (index).
-    fn update<U>(&mut self, index: X, mut update: U) -> Option<&I>
+    fn update<U>(&mut self, index: X, update: U) -> Option<&I>
     where
         U: FnMut(&mut I),
     {
-        self.items.get_mut(&index).map(|item| {
-            let key = (self.field)(item);
-            update(item);
-            self.store.update(key, index, (self.field)(item));
-            &*item
-        })
+        Map::update(self, index, update)
     }
 
     /// The Item in the Map will be removed.
     fn remove(&mut self, index: X) -> Option<I> {
-        self.items.remove(&index, |index, item| {
-            self.store.delete((self.field)(item), index);
-        })
+        Map::remove(self, index)
     }
 
     fn get_indices_by_key(&self, key: &Self::Key) -> &[Self::Index] {
@@ -164,8 +405,8 @@ where
     }
 }
 
-impl<S, I, X, F> Deref for Map<S, I, X, F> {
-    type Target = HashMap<X, I>;
+impl<S, I, X, F, M> Deref for Map<S, I, X, F, M> {
+    type Target = M;
 
     fn deref(&self) -> &Self::Target {
         &self.items.0
@@ -243,4 +484,17 @@ mod tests {
         assert!(!m.insert("Mrs X", Person::new(-3, "Mrs X")));
         assert_eq!(1, m.len());
     }
+
+    #[test]
+    fn replace() {
+        let mut m = Map::<MultiIntIndex<i32, &'static str>, Person, _, _>::new(|p| p.id);
+        assert_eq!(None, m.replace("Mrs X", Person::new(-3, "Mrs X")));
+        assert_eq!(
+            Some(Person::new(-3, "Mrs X")),
+            m.replace("Mrs X", Person::new(5, "Mrs Y"))
+        );
+        assert_eq!(1, m.len());
+        assert!(!m.idx().contains(&-3));
+        assert_eq!(Some(&Person::new(5, "Mrs Y")), m.idx().get(&5).next());
+    }
 }
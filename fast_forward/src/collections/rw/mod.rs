@@ -1,13 +1,72 @@
 //! read-write collections.
 //!
+pub mod concurrent;
+pub mod entry;
 pub mod list;
 pub mod list_base;
+pub mod map;
 pub mod map_base;
+pub mod observer;
+pub mod oplog;
+pub mod slab;
+pub mod snapshot;
+pub mod transaction;
 
 pub use list::IList;
+pub use map::IMap;
 
 use std::marker::PhantomData;
 
+/// The strategy for removing an `Item` from a [`crate::collections::rw::IList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RemoveMode {
+    /// The removed `Item` is replaced by the last `Item` ([`std::vec::Vec::swap_remove`]).
+    /// This is fast, but the order of the `Items` is changed.
+    #[default]
+    Swap,
+    /// The `Items` after the removed `Item` are shifted ([`std::vec::Vec::remove`])
+    /// and the positions in the `Store` are rewritten. The order of the `Items` is preserved.
+    Shift,
+    /// The `Items` after the removed `Item` are shifted ([`std::vec::Vec::remove`]),
+    /// but the positions in the `Store` are only rewritten by calling `compact`.
+    /// Until then, the removed positions are saved as tombstones. The order of the `Items` is preserved.
+    ///
+    /// So the `Indices` of the `Stores` (e.g. from `idx().eq(..)`) are not the positions in the List:
+    /// `update` and `remove` take the position in the List, `update_index` and `remove_index`
+    /// take the `Index` of the `Store` and `position` converts an `Index` into the position in the List.
+    /// For mapping `Indices` to `Items` use the List (e.g. `indices.items(&l)`, `iter_indexed`), not the slice `&l[..]`.
+    Tombstone,
+}
+
+/// The policy for inserting an `Item` into a [`crate::collections::rw::IMap`] with an existing `Key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InsertMode {
+    /// The insert is ignored, the existing `Item` stays in the Map.
+    #[default]
+    Ignore,
+    /// The existing `Item` is replaced by the new `Item` (like [`std::collections::HashMap::insert`]).
+    Replace,
+}
+
+/// The result of an `insert` into a [`crate::collections::rw::IMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Insert<I> {
+    /// The `Key` was new, the `Item` is inserted.
+    Inserted,
+    /// The `Key` already exists ([`InsertMode::Replace`]): the replaced `Item` is returned.
+    Replaced(I),
+    /// The `Key` already exists ([`InsertMode::Ignore`]): the given `Item` is returned and the Map is not changed.
+    Ignored(I),
+}
+
+/// The result of an `upsert`, the `Item` was inserted or updated on the given position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upsert {
+    Inserted(usize),
+    Updated(usize),
+}
+
 /// `Editable` describe the operations for changing (update and remove) `Items` in a collection.
 pub trait Editable<I> {
     type Key;
@@ -33,7 +92,8 @@ pub struct Editor<'a, I, E> {
 
 impl<'a, I, E> Editor<'a, I, E>
 where
-    E: Editable<I, Index = usize>,
+    E: Editable<I>,
+    E::Index: Clone,
 {
     pub fn new(editor: &'a mut E) -> Self {
         Self {
@@ -70,8 +130,8 @@ where
 
     /// Remove all items by a given `Key`.
     pub fn remove_by_key(&mut self, key: &E::Key) {
-        while let Some(idx) = self.editor.get_indices_by_key(key).iter().next() {
-            self.editor.remove(*idx);
+        while let Some(idx) = self.editor.get_indices_by_key(key).first().cloned() {
+            self.editor.remove(idx);
         }
     }
 
@@ -80,8 +140,8 @@ where
     where
         C: FnMut(I),
     {
-        while let Some(idx) = self.editor.get_indices_by_key(key).iter().next() {
-            if let Some(item) = self.editor.remove(*idx) {
+        while let Some(idx) = self.editor.get_indices_by_key(key).first().cloned() {
+            if let Some(item) = self.editor.remove(idx) {
                 callback(item);
             }
         }
@@ -0,0 +1,98 @@
+//! Observer hooks for [`crate::collections::rw::IList`] and [`crate::collections::rw::IMap`].
+//!
+//! An observer is a function, which is called with an [`Event`] after every change of an `Item`.
+//! So caches, audit logs or UI states can be kept in sync with the collection.
+//!
+//! The observer must be `Send` and `Sync`, so the collection can be shared between threads.
+//!
+//! The `Index` of an `Event` is the position in the List (or the `Key` in the Map) at the time of the change.
+//! After a `Removed` event, the `Items` after the removed position are shifted, like [`std::vec::Vec::remove`].
+//! By a [`crate::collections::rw::RemoveMode::Swap`], the `Removed` event is followed by a `Moved` event:
+//! the last `Item` (`from` is the last position before the remove) is moved to the removed position.
+//!
+use std::fmt::{self, Debug};
+
+/// A change of an `Item` in a read-write collection.
+#[derive(Debug, PartialEq)]
+pub enum Event<'a, I, X> {
+    /// The `Item` was inserted on the given `index`.
+    Inserted { index: X, item: &'a I },
+    /// The `Item` on the given `index` was changed from `old` to `new`.
+    Updated { index: X, old: &'a I, new: &'a I },
+    /// The `Item` on the given `index` was removed.
+    Removed { index: X, item: &'a I },
+    /// The `Item` was moved from the position `from` to the position `to`,
+    /// e.g. the last `Item` by a [`crate::collections::rw::RemoveMode::Swap`].
+    Moved { from: X, to: X },
+}
+
+/// The id of a registered observer, to unsubscribe the observer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(usize);
+
+type Observer<I, X> = Box<dyn FnMut(&Event<'_, I, X>) + Send + Sync>;
+
+/// The registered observers of a collection.
+pub(crate) struct Observers<I, X> {
+    observers: Vec<(ObserverId, Observer<I, X>)>,
+    next_id: usize,
+    // to create the `old` Item for an `Updated` event
+    clone: Option<fn(&I) -> I>,
+}
+
+impl<I, X> Observers<I, X> {
+    pub(crate) fn subscribe<O>(&mut self, observer: O) -> ObserverId
+    where
+        O: FnMut(&Event<'_, I, X>) + Send + Sync + 'static,
+        I: Clone,
+    {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.clone = Some(I::clone);
+        self.observers.push((id, Box::new(observer)));
+        id
+    }
+
+    pub(crate) fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|(i, _)| *i != id);
+        len != self.observers.len()
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    /// A copy of the `Item` before a change, only if there are observers.
+    #[inline]
+    pub(crate) fn snapshot(&self, item: &I) -> Option<I> {
+        if self.is_empty() {
+            return None;
+        }
+        self.clone.map(|clone| clone(item))
+    }
+
+    #[inline]
+    pub(crate) fn notify(&mut self, event: Event<'_, I, X>) {
+        self.observers.iter_mut().for_each(|(_, o)| o(&event));
+    }
+}
+
+impl<I, X> Default for Observers<I, X> {
+    fn default() -> Self {
+        Self {
+            observers: Vec::new(),
+            next_id: 0,
+            clone: None,
+        }
+    }
+}
+
+impl<I, X> Debug for Observers<I, X> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observers")
+            .field("len", &self.observers.len())
+            .finish()
+    }
+}
@@ -0,0 +1,352 @@
+//! Operation log (change-data-capture) for [`crate::collections::rw::IList`] and [`crate::collections::rw::IMap`].
+//!
+//! The [`OpLog`] records every change of a collection as an [`Op`], it is an observer
+//! (see: [`crate::collections::rw::observer`]), which is registered with `subscribe`.
+//! The log can be written to (and read from) a text file, one `Op` per line,
+//! truncated with a `checkpoint` and replayed (`IList::replay`, `IMap::replay`),
+//! to rebuild the same `Items` with the same `Key` lookups.
+//! The tombstones of a [`crate::collections::rw::RemoveMode::Tombstone`] are not recorded,
+//! so the `Indices` in the `Stores` of the replayed List can be different.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use fast_forward::{index::MapIndex, collections::rw::{oplog::OpLog, IList}};
+//!
+//! let log = Arc::new(Mutex::new(OpLog::new()));
+//!
+//! let mut l = IList::<MapIndex<String>, String, _>::new(|s| s.clone());
+//! l.subscribe(OpLog::recorder(&log));
+//! l.push("a".into());
+//! l.push("b".into());
+//! l.update(0, |s| *s = "c".into());
+//!
+//! // write and read the log, e.g. from a file
+//! let mut file = Vec::new();
+//! log.lock().unwrap().write_to(&mut file).unwrap();
+//! let read = OpLog::<String>::read_from(file.as_slice()).unwrap();
+//!
+//! let mut copy = IList::<MapIndex<String>, String, _>::new(|s| s.clone());
+//! copy.replay(&read);
+//! assert_eq!(&["c", "b"], &copy[..]);
+//! assert!(copy.idx().contains(&"c".into()));
+//! assert!(!copy.idx().contains(&"a".into()));
+//! ```
+//!
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead, Write},
+    ops::Deref,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use crate::collections::rw::observer::Event;
+
+/// A recorded change of a collection, the `index` is the position in the List or the `Key` in the Map.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Op<I, X = usize> {
+    Insert {
+        index: X,
+        item: I,
+    },
+    Update {
+        index: X,
+        item: I,
+    },
+    Remove {
+        index: X,
+    },
+    /// The last `Item` is moved to the removed position, follows a `Remove` by a swap-remove.
+    Move {
+        from: X,
+        to: X,
+    },
+}
+
+impl<I, X> Op<I, X>
+where
+    I: Clone,
+    X: Clone,
+{
+    fn from_event(event: &Event<'_, I, X>) -> Self {
+        match event {
+            Event::Inserted { index, item } => Op::Insert {
+                index: index.clone(),
+                item: (*item).clone(),
+            },
+            Event::Updated { index, new, .. } => Op::Update {
+                index: index.clone(),
+                item: (*new).clone(),
+            },
+            Event::Removed { index, .. } => Op::Remove {
+                index: index.clone(),
+            },
+            Event::Moved { from, to } => Op::Move {
+                from: from.clone(),
+                to: to.clone(),
+            },
+        }
+    }
+}
+
+/// One line (without the newline): the operation (`+`, `~`, `-`, `>`) and the values, separated by a tab.
+/// Tabs, newlines and backslashes in the values are escaped.
+impl<I, X> Display for Op<I, X>
+where
+    I: Display,
+    X: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let esc = |v: &dyn Display| escape(&v.to_string());
+        match self {
+            Op::Insert { index, item } => write!(f, "+\t{}\t{}", esc(index), esc(item)),
+            Op::Update { index, item } => write!(f, "~\t{}\t{}", esc(index), esc(item)),
+            Op::Remove { index } => write!(f, "-\t{}", esc(index)),
+            Op::Move { from, to } => write!(f, ">\t{}\t{}", esc(from), esc(to)),
+        }
+    }
+}
+
+impl<I, X> FromStr for Op<I, X>
+where
+    I: FromStr,
+    X: FromStr,
+{
+    type Err = ParseOpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseOpError(s.to_string());
+        let mut fields = s.split('\t');
+        let op = fields.next().ok_or_else(err)?;
+
+        let mut value =
+            || -> Result<String, ParseOpError> { fields.next().and_then(unescape).ok_or_else(err) };
+        let index = value()?.parse().map_err(|_| err())?;
+
+        let op = match op {
+            "+" | "~" => {
+                let item = value()?.parse().map_err(|_| err())?;
+                if op == "+" {
+                    Op::Insert { index, item }
+                } else {
+                    Op::Update { index, item }
+                }
+            }
+            "-" => Op::Remove { index },
+            ">" => Op::Move {
+                from: index,
+                to: value()?.parse().map_err(|_| err())?,
+            },
+            _ => return Err(err()),
+        };
+
+        if fields.next().is_some() {
+            return Err(err());
+        }
+        Ok(op)
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => r.push_str("\\\\"),
+            '\t' => r.push_str("\\t"),
+            '\n' => r.push_str("\\n"),
+            '\r' => r.push_str("\\r"),
+            c => r.push(c),
+        }
+    }
+    r
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut r = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            r.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => r.push('\\'),
+            't' => r.push('\t'),
+            'n' => r.push('\n'),
+            'r' => r.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(r)
+}
+
+/// The line can not be parsed into an [`Op`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOpError(String);
+
+impl Display for ParseOpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid operation: `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseOpError {}
+
+/// A log of all [`Op`]s of a collection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpLog<I, X = usize> {
+    ops: Vec<Op<I, X>>,
+}
+
+impl<I, X> OpLog<I, X> {
+    pub fn new() -> Self {
+        Self { ops: Vec::new() }
+    }
+
+    /// Append the `Op` to the log.
+    pub fn append(&mut self, op: Op<I, X>) {
+        self.ops.push(op);
+    }
+
+    /// Append the change of the given [`Event`] to the log.
+    pub fn record(&mut self, event: &Event<'_, I, X>)
+    where
+        I: Clone,
+        X: Clone,
+    {
+        self.ops.push(Op::from_event(event));
+    }
+
+    /// Returns an observer, which records all changes in the given log.
+    pub fn recorder(log: &Arc<Mutex<Self>>) -> impl FnMut(&Event<'_, I, X>) + Send + Sync + 'static
+    where
+        I: Clone + Send + 'static,
+        X: Clone + Send + 'static,
+    {
+        let log = log.clone();
+        move |event| log.lock().expect("op log lock").record(event)
+    }
+
+    /// Truncate the log: all `Ops` are replaced by an `Insert` for every given `Item`
+    /// (the current `Items` of the collection).
+    pub fn checkpoint<It>(&mut self, items: It)
+    where
+        It: IntoIterator<Item = (X, I)>,
+    {
+        self.ops = items
+            .into_iter()
+            .map(|(index, item)| Op::Insert { index, item })
+            .collect();
+    }
+
+    /// Write all `Ops`, one `Op` per line.
+    pub fn write_to<W>(&self, w: W) -> io::Result<()>
+    where
+        W: Write,
+        I: Display,
+        X: Display,
+    {
+        self.write_from(0, w)
+    }
+
+    /// Write the `Ops` starting with the given position in the log,
+    /// e.g. to append only the new `Ops` to a file.
+    pub fn write_from<W>(&self, start: usize, mut w: W) -> io::Result<()>
+    where
+        W: Write,
+        I: Display,
+        X: Display,
+    {
+        for op in self.ops.iter().skip(start) {
+            writeln!(w, "{op}")?;
+        }
+        w.flush()
+    }
+
+    /// Read a log, which is written with `write_to`.
+    pub fn read_from<R>(r: R) -> io::Result<Self>
+    where
+        R: BufRead,
+        I: FromStr,
+        X: FromStr,
+    {
+        let mut ops = Vec::new();
+        for line in r.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            ops.push(
+                line.parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            );
+        }
+        Ok(Self { ops })
+    }
+}
+
+impl<I, X> Default for OpLog<I, X> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, X> Deref for OpLog<I, X> {
+    type Target = [Op<I, X>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.ops
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::insert(Op::Insert { index: 1, item: "a".into() }, "+\t1\ta")]
+    #[case::update(Op::Update { index: 0, item: "a\tb\\c\nd".into() }, "~\t0\ta\\tb\\\\c\\nd")]
+    #[case::remove(Op::Remove { index: 3 }, "-\t3")]
+    #[case::moved(Op::Move { from: 5, to: 2 }, ">\t5\t2")]
+    fn display_from_str(#[case] op: Op<String>, #[case] line: &str) {
+        assert_eq!(line, op.to_string());
+        assert_eq!(Ok(op), line.parse());
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::unknown_op("*\t1")]
+    #[case::invalid_index("-\tx")]
+    #[case::missing_item("+\t1")]
+    #[case::too_many_values("-\t1\t2")]
+    #[case::invalid_escape("+\t1\ta\\x")]
+    fn parse_error(#[case] line: &str) {
+        assert_eq!(Err(ParseOpError(line.into())), line.parse::<Op<String>>());
+    }
+
+    #[test]
+    fn checkpoint() {
+        let mut log = OpLog::new();
+        log.append(Op::Insert {
+            index: 0,
+            item: 'a',
+        });
+        log.append(Op::Remove { index: 0 });
+        log.append(Op::Insert {
+            index: 0,
+            item: 'b',
+        });
+
+        log.checkpoint([(0, 'b')]);
+        assert_eq!(
+            &[Op::Insert {
+                index: 0,
+                item: 'b'
+            }],
+            &log[..]
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! read-write list with stable [`Handle`]s.
+//!
+//! The [`crate::collections::rw::IList`] use the position in the list as `Index`.
+//! A `remove` is a `swap_remove`, so the position of the last `Item` is changed
+//! and all positions, which a caller holds, can point to a different `Item`.
+//!
+//! The [`SlabList`] use a [`Handle`] (slot and generation) as `Index`, which stays valid across removals.
+//! A removed slot is reused by the next `push`, with a new generation.
+//! If the generation of a slot is exhausted (`u32::MAX`), the slot is retired and never reused.
+//! So a stale `Handle` (the `Item` was removed) is detected and never points to a different `Item`.
+//!
+use crate::{
+    collections::{
+        rw::{Editable, Editor},
+        Retriever,
+    },
+    index::{store::Store, Indexable},
+};
+
+/// A stable reference to an `Item` in a [`Slab`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle {
+    slot: usize,
+    generation: u32,
+}
+
+#[derive(Debug)]
+struct Slot<I> {
+    generation: u32,
+    item: Option<I>,
+}
+
+/// A list of `Items`, which are accessed by a [`Handle`].
+#[derive(Debug)]
+pub struct Slab<I> {
+    slots: Vec<Slot<I>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<I> Slab<I> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Insert the `Item` and return the `Handle` for this `Item`.
+    pub fn insert(&mut self, item: I) -> Handle {
+        self.len += 1;
+
+        match self.free.pop() {
+            Some(slot) => {
+                let s = &mut self.slots[slot];
+                s.item = Some(item);
+                Handle {
+                    slot,
+                    generation: s.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    item: Some(item),
+                });
+                Handle {
+                    slot: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Remove the `Item`, returns `None`, if the `Handle` is stale.
+    pub fn remove(&mut self, handle: Handle) -> Option<I> {
+        let s = self.slots.get_mut(handle.slot)?;
+        if s.generation != handle.generation {
+            return None;
+        }
+
+        let item = s.item.take()?;
+        // a wrapped generation could make a stale Handle valid again, so retire the slot
+        if let Some(generation) = s.generation.checked_add(1) {
+            s.generation = generation;
+            self.free.push(handle.slot);
+        }
+        self.len -= 1;
+        Some(item)
+    }
+
+    /// Returns the `Item` or `None`, if the `Handle` is stale.
+    pub fn get(&self, handle: Handle) -> Option<&I> {
+        self.slots
+            .get(handle.slot)
+            .filter(|s| s.generation == handle.generation)
+            .and_then(|s| s.item.as_ref())
+    }
+
+    /// Returns the `Item` or `None`, if the `Handle` is stale.
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut I> {
+        self.slots
+            .get_mut(handle.slot)
+            .filter(|s| s.generation == handle.generation)
+            .and_then(|s| s.item.as_mut())
+    }
+
+    /// Checks whether the `Handle` is valid (not stale).
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over all `Items` with the `Handle`s.
+    pub fn iter(&self) -> impl Iterator<Item = (Handle, &I)> {
+        self.slots.iter().enumerate().filter_map(|(slot, s)| {
+            s.item.as_ref().map(|i| {
+                (
+                    Handle {
+                        slot,
+                        generation: s.generation,
+                    },
+                    i,
+                )
+            })
+        })
+    }
+}
+
+impl<I> Default for Slab<I> {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl<I> Indexable<Handle> for Slab<I> {
+    type Output = I;
+
+    /// #Panic
+    ///
+    /// If the `Handle` is stale.
+    fn item(&self, handle: &Handle) -> &Self::Output {
+        self.get(*handle).expect("stale handle")
+    }
+}
+
+/// [`SlabList`] is a read write indexed `List` with stable [`Handle`]s.
+/// The `Store` saves `Handle`s instead of positions.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::{
+///     index::MapIndex,
+///     collections::rw::slab::{Handle, SlabList},
+/// };
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Car(usize, String);
+///
+/// let mut l = SlabList::<MapIndex<String, Handle>, _, _>::new(|c: &Car| c.1.clone());
+/// let bmw = l.push(Car(1, "BMW".into()));
+/// let vw = l.push(Car(2, "VW".into()));
+///
+/// assert_eq!(Some(Car(1, "BMW".into())), l.remove(bmw));
+///
+/// // the handle of VW is still valid
+/// assert_eq!(Some(&Car(2, "VW".into())), l.get(vw));
+/// assert_eq!(Some(&Car(2, "VW".into())), l.idx().get(&"VW".into()).next());
+///
+/// // the handle of BMW is stale, also if the slot is reused
+/// let audi = l.push(Car(3, "Audi".into()));
+/// assert_eq!(None, l.get(bmw));
+/// assert_eq!(Some(&Car(3, "Audi".into())), l.get(audi));
+/// ```
+#[derive(Debug)]
+pub struct SlabList<S, I, F> {
+    field: F,
+    store: S,
+    items: Slab<I>,
+}
+
+impl<S, I, F> SlabList<S, I, F>
+where
+    S: Store<Index = Handle>,
+    F: Fn(&I) -> S::Key,
+{
+    pub fn new(field: F) -> Self {
+        Self {
+            field,
+            store: S::with_capacity(0),
+            items: Slab::with_capacity(0),
+        }
+    }
+
+    pub fn from_vec(field: F, v: Vec<I>) -> Self {
+        let mut s = Self {
+            field,
+            store: S::with_capacity(v.len()),
+            items: Slab::with_capacity(v.len()),
+        };
+        v.into_iter().for_each(|item| {
+            s.push(item);
+        });
+        s
+    }
+
+    /// Append a new `Item` to the List and returns the `Handle`.
+    pub fn push(&mut self, item: I) -> Handle {
+        let key = (self.field)(&item);
+        let handle = self.items.insert(item);
+        self.store.insert(key, handle);
+        handle
+    }
+
+    /// Returns the `Item` or `None`, if the `Handle` is stale.
+    pub fn get(&self, handle: Handle) -> Option<&I> {
+        self.items.get(handle)
+    }
+
+    /// Checks whether the `Handle` is valid (not stale).
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.items.contains(handle)
+    }
+
+    /// Update the item for the given `Handle`.
+    pub fn update<U>(&mut self, handle: Handle, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        Editable::update(self, handle, update)
+    }
+
+    /// Remove the item for the given `Handle`, all other `Handle`s stay valid.
+    pub fn remove(&mut self, handle: Handle) -> Option<I> {
+        Editable::remove(self, handle)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterate over all `Items` with the `Handle`s.
+    pub fn iter(&self) -> impl Iterator<Item = (Handle, &I)> {
+        self.items.iter()
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, Slab<I>> {
+        Retriever::new(&self.store, &self.items)
+    }
+
+    pub fn idx_mut(&mut self) -> Editor<'_, I, Self> {
+        Editor::new(self)
+    }
+}
+
+impl<S, I, F> Editable<I> for SlabList<S, I, F>
+where
+    S: Store<Index = Handle>,
+    F: Fn(&I) -> S::Key,
+{
+    type Key = S::Key;
+    type Index = Handle;
+
+    fn update<U>(&mut self, handle: Handle, mut update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.items.get_mut(handle).map(|item| {
+            let key = (self.field)(item);
+            update(item);
+            self.store.update(key, handle, (self.field)(item));
+            &*item
+        })
+    }
+
+    fn remove(&mut self, handle: Handle) -> Option<I> {
+        let item = self.items.remove(handle)?;
+        self.store.delete((self.field)(&item), &handle);
+        Some(item)
+    }
+
+    fn get_indices_by_key(&self, key: &Self::Key) -> &[Self::Index] {
+        self.store.get(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::{MapIndex, MultiUIntIndex};
+
+    #[derive(Debug, PartialEq)]
+    struct Car(usize, &'static str);
+
+    #[test]
+    fn slab() {
+        let mut s = Slab::default();
+        let a = s.insert("A");
+        let b = s.insert("B");
+        assert_eq!(2, s.len());
+
+        assert_eq!(Some("A"), s.remove(a));
+        assert_eq!(None, s.remove(a));
+        assert!(!s.contains(a));
+        assert_eq!(Some(&"B"), s.get(b));
+
+        // reuse the slot with a new generation
+        let c = s.insert("C");
+        assert_eq!(a.slot, c.slot);
+        assert_ne!(a, c);
+        assert_eq!(None, s.get(a));
+        assert_eq!(Some(&"C"), s.get(c));
+        assert_eq!(vec![(c, &"C"), (b, &"B")], s.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn retire_exhausted_slot() {
+        let mut s = Slab::default();
+        let a = s.insert("A");
+        s.slots[a.slot].generation = u32::MAX;
+        let a = Handle {
+            slot: a.slot,
+            generation: u32::MAX,
+        };
+
+        assert_eq!(Some("A"), s.remove(a));
+        assert!(!s.contains(a));
+        assert!(s.is_empty());
+
+        // the slot is not reused
+        let b = s.insert("B");
+        assert_ne!(a.slot, b.slot);
+        assert!(!s.contains(a));
+        assert_eq!(vec![(b, &"B")], s.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "stale handle")]
+    fn item_stale_handle() {
+        let mut s = Slab::default();
+        let a = s.insert("A");
+        s.remove(a);
+        s.item(&a);
+    }
+
+    #[test]
+    fn handles_stay_valid() {
+        let mut l = SlabList::<MultiUIntIndex<usize, Handle>, _, _>::from_vec(
+            |c| c.0,
+            vec![
+                Car(2, "BMW"),
+                Car(5, "Audi"),
+                Car(2, "VW"),
+                Car(99, "Porsche"),
+            ],
+        );
+        let handles = l.iter().map(|(h, _)| h).collect::<Vec<_>>();
+
+        assert_eq!(Some(Car(2, "BMW")), l.remove(handles[0]));
+        assert_eq!(None, l.remove(handles[0]));
+
+        // the handle of the last item is not changed
+        assert_eq!(Some(&Car(99, "Porsche")), l.get(handles[3]));
+        assert_eq!(vec![&Car(2, "VW")], l.idx().get(&2).collect::<Vec<_>>());
+        assert_eq!(3, l.len());
+
+        // update
+        assert_eq!(Some(&Car(5, "VW")), l.update(handles[2], |c| c.0 = 5));
+        assert_eq!(None, l.update(handles[0], |c| c.0 = 5));
+        assert_eq!(
+            vec![&Car(5, "Audi"), &Car(5, "VW")],
+            l.idx().get(&5).collect::<Vec<_>>()
+        );
+        assert!(!l.idx().contains(&2));
+    }
+
+    #[test]
+    fn editor() {
+        let mut l = SlabList::<MapIndex<&str, Handle>, _, _>::new(|c: &Car| c.1);
+        l.push(Car(1, "BMW"));
+        let vw = l.push(Car(2, "VW"));
+        l.push(Car(3, "BMW"));
+
+        let mut removed = Vec::new();
+        l.idx_mut()
+            .remove_by_key_with_cb(&"BMW", |c| removed.push(c));
+        assert_eq!(vec![Car(1, "BMW"), Car(3, "BMW")], removed);
+
+        l.idx_mut().update_by_key(&"VW", |c| c.0 = 20);
+        assert_eq!(Some(&Car(20, "VW")), l.get(vw));
+        assert_eq!(1, l.len());
+    }
+}
@@ -0,0 +1,348 @@
+//! read-write list with point-in-time [`Snapshot`]s (copy-on-write).
+//!
+//! A [`Retriever`] borrows the List, so a long-running query blocks all writers.
+//! A [`Snapshot`] is a version of the `Items` and the `Stores`, which is shared with the [`SnapshotList`].
+//! Creating a `Snapshot` is cheap (an [`Arc`] clone). The first write after creating a `Snapshot`
+//! copies the `Items` and the `Stores`, all next writes change the copy,
+//! until the next `Snapshot` is created.
+//! So a query on a `Snapshot` sees a consistent version, while the writer continues.
+//!
+//! A `Snapshot` is released, if it is dropped (or by calling `release`).
+//! If all `Snapshots` are released, the writer doesn't copy anymore.
+//!
+use std::{ops::Deref, sync::Arc};
+
+use crate::{
+    collections::{
+        rw::{list::IList, list_base::ItemList, RemoveMode, Upsert},
+        Retriever,
+    },
+    index::{
+        store::{Filterable, Indexer},
+        Indexable,
+    },
+};
+
+/// [`SnapshotList`] is a read write indexed `List`, which creates [`Snapshot`]s for reading.
+///
+/// ## Hint:
+/// The first write after creating a `Snapshot` copies all `Items` and `Stores`, this costs O(n).
+/// So it is fast, if many writes follow a `Snapshot`, but slow, if a `Snapshot` is created before every write.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::{index::MapIndex, collections::rw::snapshot::SnapshotList};
+///
+/// let mut l = SnapshotList::<MapIndex<&str>, _, _>::from_vec(|s| *s, vec!["a", "b"]);
+///
+/// let snapshot = l.snapshot();
+/// assert_eq!(1, l.snapshots());
+///
+/// // the first write copies the Items and the Stores
+/// l.update(0, |s| *s = "c");
+/// l.push("d");
+///
+/// // the snapshot is not changed by the writer
+/// assert_eq!(&["a", "b"], &snapshot[..]);
+/// assert!(snapshot.idx().contains(&"a"));
+/// assert!(!snapshot.idx().contains(&"c"));
+///
+/// assert_eq!(&["c", "b", "d"], &l[..]);
+/// assert!(l.idx().contains(&"c"));
+/// assert_eq!(0, l.snapshots());
+///
+/// snapshot.release();
+/// ```
+#[derive(Debug)]
+pub struct SnapshotList<S, I, F> {
+    list: Arc<IList<S, I, F>>,
+    version: u64,
+}
+
+impl<S, I, F> SnapshotList<S, I, F>
+where
+    S: Indexer<I, F, Index = usize> + Clone,
+    I: Clone,
+    F: Clone,
+{
+    pub fn new(field: F) -> Self {
+        Self {
+            list: Arc::new(IList::new(field)),
+            version: 0,
+        }
+    }
+
+    pub fn from_vec(field: F, v: Vec<I>) -> Self {
+        Self {
+            list: Arc::new(IList::from_vec(field, v)),
+            version: 0,
+        }
+    }
+
+    /// Set the [`RemoveMode`] for this List.
+    pub fn with_remove_mode(self, mode: RemoveMode) -> Self {
+        Self {
+            list: Arc::new(IList::unwrap_or_clone(self.list).with_remove_mode(mode)),
+            version: self.version,
+        }
+    }
+
+    /// The `List` for a change, it is copied, if a `Snapshot` exists.
+    fn write(&mut self) -> &mut IList<S, I, F> {
+        self.version += 1;
+        IList::make_mut(&mut self.list)
+    }
+
+    /// Append a new `Item` to the List.
+    pub fn push(&mut self, item: I) -> usize {
+        self.write().push(item)
+    }
+
+    /// Insert the `Item`, if the `Key` of the primary `Store` is new, otherwise the `Item` is replaced.
+    pub fn upsert(&mut self, item: I) -> Upsert {
+        self.write().upsert(item)
+    }
+
+    /// Update the item on the given position.
+    pub fn update<U>(&mut self, pos: usize, update: U) -> Option<&I>
+    where
+        U: FnMut(&mut I),
+    {
+        self.write().update(pos, update)
+    }
+
+    /// The Item on the given position will be removed from the list.
+    pub fn remove(&mut self, pos: usize) -> Option<I> {
+        self.write().remove(pos)
+    }
+
+    /// Create a [`Snapshot`] of the current version.
+    pub fn snapshot(&self) -> Snapshot<S, I, F> {
+        Snapshot::new(Arc::clone(&self.list), self.version)
+    }
+
+    /// The current version, every write creates a new version.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The number of not released `Snapshots` of the current version.
+    pub fn snapshots(&self) -> usize {
+        Arc::strong_count(&self.list) - 1
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, ItemList<I>>
+    where
+        S: Filterable,
+    {
+        self.list.idx()
+    }
+
+    /// Returns the [`Retriever`] for the selected `Store`, if the List has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, ItemList<I>>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        self.list.idx_by(select)
+    }
+}
+
+/// Maps the `Indices` of the `Stores` to the `Items` (see: [`IList`]).
+impl<S, I, F> Indexable<usize> for SnapshotList<S, I, F> {
+    type Output = I;
+
+    fn item(&self, idx: &usize) -> &Self::Output {
+        self.list.item(idx)
+    }
+}
+
+impl<S, I, F> Deref for SnapshotList<S, I, F> {
+    type Target = [I];
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
+
+/// A read-only version of the `Items` and the `Stores` of a [`SnapshotList`].
+/// A `Snapshot` can be sent to an other thread (if the `Items`, `Stores` and the key function are `Send` and `Sync`).
+#[derive(Debug)]
+pub struct Snapshot<S, I, F> {
+    list: Arc<IList<S, I, F>>,
+    version: u64,
+}
+
+impl<S, I, F> Snapshot<S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    pub(crate) fn new(list: Arc<IList<S, I, F>>, version: u64) -> Self {
+        Self { list, version }
+    }
+
+    /// The version of the `SnapshotList`, when the `Snapshot` was created.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn idx(&self) -> Retriever<'_, S, ItemList<I>>
+    where
+        S: Filterable,
+    {
+        self.list.idx()
+    }
+
+    /// Returns the [`Retriever`] for the selected `Store`, if the List has a tuple of `Stores`.
+    pub fn idx_by<R, Select>(&self, select: Select) -> Retriever<'_, R, ItemList<I>>
+    where
+        R: Filterable,
+        Select: FnOnce(&S) -> &R,
+    {
+        self.list.idx_by(select)
+    }
+
+    /// Iterate over all `Items` with the `Index` of the `Store` (see: [`IList::iter_indexed`]).
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &I)> {
+        self.list.iter_indexed()
+    }
+
+    /// Release the `Snapshot`, the same as `drop`.
+    pub fn release(self) {}
+}
+
+impl<S, I, F> Clone for Snapshot<S, I, F> {
+    fn clone(&self) -> Self {
+        Self {
+            list: Arc::clone(&self.list),
+            version: self.version,
+        }
+    }
+}
+
+/// Maps the `Indices` of the `Stores` to the `Items` (see: [`IList`]).
+impl<S, I, F> Indexable<usize> for Snapshot<S, I, F> {
+    type Output = I;
+
+    fn item(&self, idx: &usize) -> &Self::Output {
+        self.list.item(idx)
+    }
+}
+
+impl<S, I, F> Deref for Snapshot<S, I, F> {
+    type Target = [I];
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::{MapIndex, UniqueUIntIndex};
+    use rstest::rstest;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Car(usize, &'static str);
+
+    #[allow(clippy::type_complexity)]
+    fn cars(
+        mode: RemoveMode,
+    ) -> SnapshotList<
+        (UniqueUIntIndex, MapIndex<&'static str>),
+        Car,
+        (
+            impl Fn(&Car) -> usize + Clone,
+            impl Fn(&Car) -> &'static str + Clone,
+        ),
+    > {
+        SnapshotList::from_vec(
+            (|c: &Car| c.0, |c: &Car| c.1),
+            vec![Car(1, "BMW"), Car(2, "VW"), Car(3, "Audi")],
+        )
+        .with_remove_mode(mode)
+    }
+
+    #[rstest]
+    #[case::swap(RemoveMode::Swap)]
+    #[case::tombstone(RemoveMode::Tombstone)]
+    fn isolation(#[case] mode: RemoveMode) {
+        let mut l = cars(mode);
+        let s1 = l.snapshot();
+
+        l.remove(0);
+        l.update(0, |c| c.1 = "Opel");
+        let s2 = l.snapshot();
+        l.push(Car(4, "BMW"));
+        l.upsert(Car(2, "Porsche"));
+
+        // first snapshot: the items and the stores before all changes
+        assert_eq!(&[Car(1, "BMW"), Car(2, "VW"), Car(3, "Audi")], &s1[..]);
+        assert_eq!(
+            Some(&Car(1, "BMW")),
+            s1.idx_by(|(_, n)| n).get(&"BMW").next()
+        );
+        assert!(!s1.idx_by(|(ids, _)| ids).contains(&4));
+        assert!(!s1.idx_by(|(_, n)| n).contains(&"Opel"));
+
+        // second snapshot: after the remove and the update
+        assert_eq!(2, s2.len());
+        assert!(!s2.idx_by(|(ids, _)| ids).contains(&1));
+        assert_eq!(1, s2.idx_by(|(_, n)| n).get(&"Opel").count());
+        assert!(!s2.idx_by(|(_, n)| n).contains(&"Porsche"));
+        assert!(s1.version() < s2.version());
+
+        // the current version
+        assert_eq!(3, l.len());
+        assert_eq!(
+            Some(&Car(2, "Porsche")),
+            l.idx_by(|(ids, _)| ids).get(&2).next()
+        );
+        assert_eq!(
+            Some(&Car(4, "BMW")),
+            l.idx_by(|(_, n)| n).get(&"BMW").next()
+        );
+        assert!(!l.idx_by(|(_, n)| n).contains(&"VW"));
+    }
+
+    #[test]
+    fn release() {
+        let mut l = cars(RemoveMode::Swap);
+        assert_eq!(0, l.snapshots());
+
+        let s1 = l.snapshot();
+        let s2 = s1.clone();
+        assert_eq!(2, l.snapshots());
+
+        // the snapshots share the old version, the List has a new copy
+        l.push(Car(4, "Opel"));
+        assert_eq!(0, l.snapshots());
+        assert_eq!(3, s2.len());
+        s1.release();
+        drop(s2);
+
+        // without a snapshot, the List is not copied
+        let ptr = l.as_ptr();
+        l.update(0, |c| c.0 = 9);
+        assert_eq!(ptr, l.as_ptr());
+        assert_eq!(
+            Some(&Car(9, "BMW")),
+            l.idx_by(|(ids, _)| ids).get(&9).next()
+        );
+    }
+
+    #[test]
+    fn snapshot_in_other_thread() {
+        let mut l = SnapshotList::<MapIndex<&str>, _, _>::from_vec(|s: &&str| *s, vec!["a", "b"]);
+        let s = l.snapshot();
+
+        let reader = std::thread::spawn(move || s.idx().get(&"a").count());
+        l.update(0, |s| *s = "b");
+        l.push("a");
+
+        assert_eq!(1, reader.join().unwrap());
+        assert_eq!(2, l.idx().get(&"b").count());
+    }
+}
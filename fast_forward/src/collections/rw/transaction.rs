@@ -0,0 +1,267 @@
+//! Transactions for [`crate::collections::rw::IList`] and [`crate::collections::rw::IMap`].
+//!
+//! A transaction stages `push` (`insert`), `update` and `remove` operations.
+//! On `commit`, the whole batch is validated and then applied, or nothing is applied.
+//! A `rollback` (or drop) of the transaction leaves the `Items` and the `Stores` untouched.
+//!
+//! Every unique `Store` (e.g. [`crate::index::UniqueUIntIndex`]) of the [`crate::index::store::Indexer`]
+//! is a unique constraint (see: [`crate::index::store::Unique`]).
+//!
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+use crate::{
+    collections::rw::{
+        list_base::List,
+        map_base::{Map, MapItems},
+        InsertMode,
+    },
+    index::store::{Indexer, Unique},
+};
+
+/// The reason, why a transaction can not be committed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionError {
+    /// A `Key` of a unique `Store` is not unique after applying the batch.
+    NotUnique,
+    /// An `insert` with an existing `Key`, by the [`InsertMode::Ignore`].
+    KeyExists,
+}
+
+impl Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::NotUnique => write!(f, "the key of a unique store is not unique"),
+            TransactionError::KeyExists => write!(f, "the key of the map already exists"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+/// A transaction on a [`crate::collections::rw::IList`].
+/// All positions are the positions in the List before the transaction.
+pub struct Transaction<'a, S, I, F> {
+    list: &'a mut List<S, I, F>,
+    updates: BTreeMap<usize, I>,
+    removes: BTreeSet<usize>,
+    pushes: Vec<I>,
+}
+
+impl<'a, S, I, F> Transaction<'a, S, I, F>
+where
+    S: Indexer<I, F, Index = usize>,
+{
+    pub(crate) fn new(list: &'a mut List<S, I, F>) -> Self {
+        Self {
+            list,
+            updates: BTreeMap::new(),
+            removes: BTreeSet::new(),
+            pushes: Vec::new(),
+        }
+    }
+
+    /// Stage a new `Item`, which is appended to the List.
+    pub fn push(&mut self, item: I) {
+        self.pushes.push(item);
+    }
+
+    /// Stage an update of the `Item` on the given position.
+    /// Returns `false`, if the position doesn't exist or is removed.
+    pub fn update<U>(&mut self, pos: usize, update: U) -> bool
+    where
+        U: FnOnce(&mut I),
+        I: Clone,
+    {
+        if pos >= self.list.len() || self.removes.contains(&pos) {
+            return false;
+        }
+
+        let item = self
+            .updates
+            .entry(pos)
+            .or_insert_with(|| self.list[pos].clone());
+        update(item);
+        true
+    }
+
+    /// Stage a remove of the `Item` on the given position.
+    /// Returns `false`, if the position doesn't exist or is already removed.
+    pub fn remove(&mut self, pos: usize) -> bool {
+        if pos >= self.list.len() || !self.removes.insert(pos) {
+            return false;
+        }
+        self.updates.remove(&pos);
+        true
+    }
+
+    /// The number of staged operations.
+    pub fn len(&self) -> usize {
+        self.updates.len() + self.removes.len() + self.pushes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Validate the unique constraints and apply all staged operations.
+    /// If the validation failed, the List is not changed.
+    pub fn commit(self) -> Result<(), TransactionError>
+    where
+        S: Unique<I, F>,
+    {
+        let unique = self.list.check_unique(&self.updates, &self.pushes, |pos| {
+            !self.removes.contains(&pos) && !self.updates.contains_key(&pos)
+        });
+        if !unique {
+            return Err(TransactionError::NotUnique);
+        }
+
+        self.list
+            .apply_batch(self.updates, self.removes, self.pushes);
+        Ok(())
+    }
+
+    /// Discard all staged operations.
+    pub fn rollback(self) {}
+}
+
+enum Staged<I> {
+    Insert(I),
+    Remove,
+}
+
+/// A transaction on a [`crate::collections::rw::IMap`].
+/// The operations are staged in the order of the first change of a `Key`.
+pub struct MapTransaction<'a, S, I, X, F, M> {
+    map: &'a mut Map<S, I, X, F, M>,
+    mode: InsertMode,
+    staged: Vec<(X, Staged<I>)>,
+    positions: HashMap<X, usize>,
+    key_exists: bool,
+}
+
+impl<'a, S, I, X, F, M> MapTransaction<'a, S, I, X, F, M>
+where
+    S: Indexer<I, F, Index = X>,
+    X: Hash + Eq + Clone,
+    M: MapItems<X, I>,
+{
+    pub(crate) fn new(map: &'a mut Map<S, I, X, F, M>, mode: InsertMode) -> Self {
+        Self {
+            map,
+            mode,
+            staged: Vec::new(),
+            positions: HashMap::new(),
+            key_exists: false,
+        }
+    }
+
+    fn staged(&self, key: &X) -> Option<&Staged<I>> {
+        self.positions.get(key).map(|pos| &self.staged[*pos].1)
+    }
+
+    fn stage(&mut self, key: X, op: Staged<I>) {
+        match self.positions.get(&key) {
+            Some(pos) => self.staged[*pos].1 = op,
+            None => {
+                self.positions.insert(key.clone(), self.staged.len());
+                self.staged.push((key, op));
+            }
+        }
+    }
+
+    /// The `Item` for the given `Key`, with all staged operations.
+    fn get(&self, key: &X) -> Option<&I> {
+        match self.staged(key) {
+            Some(Staged::Insert(item)) => Some(item),
+            Some(Staged::Remove) => None,
+            None => self.map.get(key),
+        }
+    }
+
+    /// Stage an insert of the `Item`.
+    /// If the `Key` already exists, it depends on the [`InsertMode`] of the Map:
+    /// - [`InsertMode::Ignore`]: the `commit` returns the error: [`TransactionError::KeyExists`]
+    /// - [`InsertMode::Replace`]: the existing `Item` is replaced
+    pub fn insert(&mut self, key: X, item: I) {
+        if self.mode == InsertMode::Ignore && self.get(&key).is_some() {
+            self.key_exists = true;
+            return;
+        }
+        self.stage(key, Staged::Insert(item));
+    }
+
+    /// Stage an update of the `Item` with the given `Key`.
+    /// Returns `false`, if the `Key` doesn't exist.
+    pub fn update<U>(&mut self, key: X, update: U) -> bool
+    where
+        U: FnOnce(&mut I),
+        I: Clone,
+    {
+        let Some(mut item) = self.get(&key).cloned() else {
+            return false;
+        };
+        update(&mut item);
+        self.stage(key, Staged::Insert(item));
+        true
+    }
+
+    /// Stage a remove of the `Item` with the given `Key`.
+    /// Returns `false`, if the `Key` doesn't exist.
+    pub fn remove(&mut self, key: X) -> bool {
+        if self.get(&key).is_none() {
+            return false;
+        }
+        self.stage(key, Staged::Remove);
+        true
+    }
+
+    /// The number of staged operations.
+    pub fn len(&self) -> usize {
+        self.staged.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.staged.is_empty()
+    }
+
+    /// Validate the [`InsertMode`] and the unique constraints and apply all staged operations.
+    /// If the validation failed, the Map is not changed.
+    pub fn commit(self) -> Result<(), TransactionError>
+    where
+        S: Unique<I, F>,
+    {
+        if self.key_exists {
+            return Err(TransactionError::KeyExists);
+        }
+
+        let changed = self.staged.iter().filter_map(|(key, s)| match s {
+            Staged::Insert(item) => Some((key.clone(), item)),
+            Staged::Remove => None,
+        });
+        if !self
+            .map
+            .check_unique(changed, |key| self.staged(key).is_none())
+        {
+            return Err(TransactionError::NotUnique);
+        }
+
+        // first remove all changed Items, so a unique Key can move between the Items
+        for (key, _) in &self.staged {
+            self.map.remove(key.clone());
+        }
+        for (key, s) in self.staged {
+            if let Staged::Insert(item) = s {
+                self.map.insert(key, item);
+            }
+        }
+        Ok(())
+    }
+
+    /// Discard all staged operations.
+    pub fn rollback(self) {}
+}
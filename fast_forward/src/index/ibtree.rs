@@ -0,0 +1,263 @@
+//! Is an `Index` which use the [`std::collections::BTreeMap`]
+//! to find the Indices for a given `Key`. The `Keys` are sorted,
+//! so this `Index` can be used for range queries and ordering.
+//!
+use crate::index::{
+    indices::{KeyIndex, MultiKeyIndex},
+    store::{Filterable, Merge, Ordered, Positions, Store, View, ViewCreator},
+};
+use std::{collections::BTreeMap, fmt::Debug, ops::RangeBounds};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// `Key` default type is [`String`] and use [`std::collections::BTreeMap`] for the Index implementation.
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct SortedIndex<K = String, X = usize>(BTreeMap<K, MultiKeyIndex<X>>);
+
+impl<K, X> Default for SortedIndex<K, X> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<K, X> Filterable for SortedIndex<K, X>
+where
+    K: Ord,
+    X: Ord + PartialEq,
+{
+    type Key = K;
+    type Index = X;
+
+    #[inline]
+    fn get(&self, key: &Self::Key) -> &[Self::Index] {
+        match self.0.get(key) {
+            Some(i) => i.as_slice(),
+            None => &[],
+        }
+    }
+
+    fn contains(&self, key: &Self::Key) -> bool {
+        self.0.contains_key(key)
+    }
+}
+
+impl<K, X> Ordered for SortedIndex<K, X>
+where
+    K: Ord,
+    X: Ord + PartialEq,
+{
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>,
+    {
+        self.0.range(range).map(|(_, i)| i.as_slice())
+    }
+}
+
+impl<'a, K, X> ViewCreator<'a> for SortedIndex<K, X>
+where
+    K: Ord,
+    X: Ord + 'a,
+{
+    type Key = K;
+    type Filter = BTreeMap<K, &'a MultiKeyIndex<X>>;
+
+    fn create_view<It>(&'a self, keys: It) -> View<Self::Filter>
+    where
+        It: IntoIterator<Item = Self::Key>,
+    {
+        let mut map = BTreeMap::<K, &MultiKeyIndex<X>>::new();
+
+        for key in keys {
+            if let Some(idxs) = self.0.get(&key) {
+                map.insert(key, idxs);
+            }
+        }
+
+        View(map)
+    }
+}
+
+impl<K, X> Store for SortedIndex<K, X>
+where
+    K: Ord,
+    X: Ord,
+{
+    fn insert(&mut self, key: K, i: Self::Index) {
+        match self.0.get_mut(&key) {
+            Some(v) => v.add(i),
+            None => {
+                self.0.insert(key, MultiKeyIndex::new(i));
+            }
+        }
+    }
+
+    fn delete(&mut self, key: K, idx: &Self::Index) {
+        if let Some(rm_idx) = self.0.get_mut(&key) {
+            if rm_idx.remove(idx) {
+                self.0.remove(&key);
+            }
+        }
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        SortedIndex(BTreeMap::new())
+    }
+}
+
+impl<K, X> Merge for SortedIndex<K, X>
+where
+    K: Ord,
+    X: Ord + Clone,
+{
+    fn merge(&mut self, other: Self) {
+        for (key, idx) in other.0 {
+            match self.0.get_mut(&key) {
+                Some(v) => v.merge(idx),
+                None => {
+                    self.0.insert(key, idx);
+                }
+            }
+        }
+    }
+}
+
+impl<K> Positions for SortedIndex<K, usize> {
+    fn max_position(&self) -> Option<usize> {
+        self.0
+            .values()
+            .filter_map(|i| i.as_slice().last())
+            .max()
+            .copied()
+    }
+
+    fn count_positions(&self) -> usize {
+        self.0.values().map(|i| i.as_slice().len()).sum()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, X> Serialize for SortedIndex<K, X>
+where
+    K: Serialize,
+    X: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, X> Deserialize<'de> for SortedIndex<K, X>
+where
+    K: Deserialize<'de> + Ord,
+    X: Deserialize<'de> + Ord,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self(BTreeMap::deserialize(deserializer)?))
+    }
+}
+
+impl<K, X> Filterable for BTreeMap<K, &MultiKeyIndex<X>>
+where
+    K: Ord,
+    X: Ord + PartialEq,
+{
+    type Key = K;
+    type Index = X;
+
+    #[inline]
+    fn get(&self, key: &Self::Key) -> &[Self::Index] {
+        match self.get(key) {
+            Some(i) => i.as_slice(),
+            None => &[],
+        }
+    }
+
+    fn contains(&self, key: &Self::Key) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, X> Ordered for BTreeMap<K, &MultiKeyIndex<X>>
+where
+    K: Ord,
+    X: Ord + PartialEq,
+{
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>,
+    {
+        BTreeMap::range(self, range).map(|(_, i)| i.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retrieve() {
+        let mut i = SortedIndex::default();
+        i.insert("Paul", 6);
+        i.insert("Jasmin", 4);
+        i.insert("Mario", 8);
+        i.insert("Jasmin", 1);
+
+        assert!(i.contains(&"Paul"));
+        assert!(!i.contains(&"Blub"));
+        assert_eq!([1, 4], i.get(&"Jasmin"));
+        assert_eq!(0, i.get(&"Blub").len());
+    }
+
+    #[test]
+    fn range() {
+        let mut i = SortedIndex::default();
+        i.insert("Paul", 6);
+        i.insert("Jasmin", 4);
+        i.insert("Mario", 8);
+        i.insert("Jasmin", 1);
+
+        assert_eq!(
+            vec![&[1, 4][..], &[8], &[6]],
+            i.range(..).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&[6][..], &[8]],
+            i.range("Mario"..).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![&[1, 4][..]], i.range(.."Mario").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn update_delete() {
+        let mut i = SortedIndex::default();
+        i.insert(3, 1);
+        i.insert(3, 2);
+
+        i.update(3, 2, 1);
+        assert_eq!([1], i.get(&3));
+        assert_eq!([2], i.get(&1));
+
+        i.delete(3, &1);
+        assert!(!i.contains(&3));
+        assert_eq!(vec![&[2][..]], i.range(..).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn create_view() {
+        let mut i = SortedIndex::default();
+        i.insert("Jasmin", 5);
+        i.insert("Jasmin", 2);
+        i.insert("Mario", 3);
+        i.insert("Paul", 4);
+
+        let view = i.create_view(["Jasmin", "Mario"]);
+        assert!(view.contains(&"Jasmin"));
+        assert!(!view.contains(&"Paul"));
+        assert_eq!(view.get(&"Jasmin"), &[2, 5]);
+        assert_eq!(view.get(&"Paul"), &[]);
+    }
+}
@@ -3,10 +3,13 @@
 //!
 use crate::index::{
     indices::{KeyIndex, MultiKeyIndex},
-    store::{Filterable, Store, View, ViewCreator},
+    store::{Filterable, Merge, Positions, Store, View, ViewCreator},
 };
 use std::{fmt::Debug, hash::Hash};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 #[cfg(feature = "hashbrown")]
 use hashbrown::HashMap;
 
@@ -14,7 +17,7 @@ use hashbrown::HashMap;
 use std::collections::HashMap;
 
 /// `Key` default type is [`String`] and use [`std::collections::HashMap`] for the Index implementation.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(transparent)]
 pub struct MapIndex<K = String, X = usize>(HashMap<K, MultiKeyIndex<X>>);
 
@@ -96,6 +99,59 @@ where
     }
 }
 
+impl<K, X> Merge for MapIndex<K, X>
+where
+    K: Hash + Eq,
+    X: Ord + Clone,
+{
+    fn merge(&mut self, other: Self) {
+        for (key, idx) in other.0 {
+            match self.0.get_mut(&key) {
+                Some(v) => v.merge(idx),
+                None => {
+                    self.0.insert(key, idx);
+                }
+            }
+        }
+    }
+}
+
+impl<K> Positions for MapIndex<K, usize> {
+    fn max_position(&self) -> Option<usize> {
+        self.0
+            .values()
+            .filter_map(|i| i.as_slice().last())
+            .max()
+            .copied()
+    }
+
+    fn count_positions(&self) -> usize {
+        self.0.values().map(|i| i.as_slice().len()).sum()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, X> Serialize for MapIndex<K, X>
+where
+    K: Serialize + Hash + Eq,
+    X: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, X> Deserialize<'de> for MapIndex<K, X>
+where
+    K: Deserialize<'de> + Hash + Eq,
+    X: Deserialize<'de> + Ord,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self(HashMap::deserialize(deserializer)?))
+    }
+}
+
 impl<K, X> Filterable for HashMap<K, &MultiKeyIndex<X>>
 where
     K: Hash + Eq,
@@ -1,12 +1,17 @@
 //! There are two kinds of `Indices`
 //! - KeyIndices: is a collection of all `Indices`for a given `Key`
 //! - Indices: is a collection (read only) of selected `Indices`,
-//! which you can use for operations like [`std::ops::BitOr`] and [`std::ops::BitAnd`].
+//!   which you can use for operations like [`std::ops::BitOr`] and [`std::ops::BitAnd`].
 use std::{
     borrow::Cow,
     ops::{BitAnd, BitOr},
 };
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
 use crate::index::{
     ops::{intersection, union},
     Indexable,
@@ -15,6 +20,9 @@ use crate::index::{
 /// `KeyIndex` is the interface for `unique` and `multi` key indices.
 /// It contains all indices for a given `Key`.
 pub trait KeyIndex<X> {
+    /// `true`, if the `KeyIndex` saves maximal one `idx`.
+    const UNIQUE: bool = false;
+
     /// Create a new `KeyIndex` with the initial value `idx`.
     fn new(idx: X) -> Self;
     /// Add a new `idx`.
@@ -23,6 +31,17 @@ pub trait KeyIndex<X> {
     fn remove(&mut self, idx: &X) -> bool;
     /// Returns all saved `idx` as slice.
     fn as_slice(&self) -> &[X];
+    /// Add all `idx` of the other `KeyIndex`.
+    fn merge(&mut self, other: Self)
+    where
+        Self: Sized,
+        X: Clone,
+    {
+        other
+            .as_slice()
+            .iter()
+            .for_each(|idx| self.add(idx.clone()))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +52,8 @@ impl<X> KeyIndex<X> for UniqueKeyIndex<X>
 where
     X: PartialEq,
 {
+    const UNIQUE: bool = true;
+
     /// Create a new Index.
     fn new(idx: X) -> Self {
         Self(Some([idx]))
@@ -62,6 +83,16 @@ where
             .as_ref()
             .map_or_else(|| [].as_slice(), |idx| idx.as_slice())
     }
+
+    /// ## Panics
+    /// Panics, if both `KeyIndex` contains an `idx`.
+    fn merge(&mut self, other: Self) {
+        match (&self.0, other.0) {
+            (_, None) => {}
+            (None, other) => self.0 = other,
+            (Some(_), Some(_)) => panic!("unique index can not add a new index"),
+        }
+    }
 }
 
 impl<X> From<[X; 1]> for UniqueKeyIndex<X> {
@@ -114,11 +145,21 @@ where
     fn as_slice(&self) -> &[X] {
         self.0.as_ref()
     }
+
+    /// If all other Indices are greater, they are appended, otherwise every Index is added.
+    fn merge(&mut self, other: Self) {
+        match (self.0.last(), other.0.first()) {
+            (Some(last), Some(first)) if last >= first => {
+                other.0.into_iter().for_each(|idx| self.add(idx))
+            }
+            _ => self.0.extend(other.0),
+        }
+    }
 }
 
 /// `Indices` is a read only collection of selected Indices.
 /// The `Indices` can be created as result from quering (filtering) a list.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 #[repr(transparent)]
 pub struct Indices<'i, I: Clone = usize>(Cow<'i, [I]>);
 
@@ -143,17 +184,63 @@ where
         self.0.as_ref()
     }
 
+    /// Returns the sub set of `Indices`, which skips the first `offset` Indices
+    /// and contains maximal `limit` Indices.
+    pub fn page(self, offset: usize, limit: usize) -> Self {
+        let len = self.0.len();
+        let start = offset.min(len);
+        let end = start.saturating_add(limit).min(len);
+
+        match self.0 {
+            Cow::Borrowed(s) => Self(Cow::Borrowed(&s[start..end])),
+            Cow::Owned(mut v) => {
+                v.truncate(end);
+                v.drain(..start);
+                Self(Cow::Owned(v))
+            }
+        }
+    }
+
     /// Is a mapping from indices to Items from an given list.
     pub fn items<Idx>(
         self,
         list: &'i Idx,
     ) -> impl Iterator<Item = &'i <Idx as Indexable<I>>::Output>
     where
-        Idx: Indexable<I>,
+        Idx: Indexable<I> + ?Sized,
     {
         #[allow(clippy::unnecessary_to_owned)]
         self.0.into_owned().into_iter().map(|i| list.item(&i))
     }
+
+    /// Is a parallel mapping (with [`rayon`]) from indices to Items from an given list.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::index::indices::Indices;
+    /// use rayon::prelude::*;
+    ///
+    /// let l = vec!["a", "b", "c", "d"];
+    /// let r = Indices::from([3, 0]).par_items(&l).map(|s| s.to_uppercase()).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec!["A", "D"], r);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_items<Idx>(
+        self,
+        list: &'i Idx,
+    ) -> impl IndexedParallelIterator<Item = &'i <Idx as Indexable<I>>::Output>
+    where
+        Idx: Indexable<I> + Sync + ?Sized,
+        <Idx as Indexable<I>>::Output: Sync,
+        I: Send,
+    {
+        self.0
+            .into_owned()
+            .into_par_iter()
+            .map(move |i| list.item(&i))
+    }
 }
 
 impl<I: Ord + Clone, const N: usize> From<[I; N]> for Indices<'_, I> {
@@ -163,6 +250,86 @@ impl<I: Ord + Clone, const N: usize> From<[I; N]> for Indices<'_, I> {
     }
 }
 
+impl<I: Ord + Clone> From<Vec<I>> for Indices<'_, I> {
+    /// Create an `Indices` from an (unsorted) Vec, duplicates are removed.
+    fn from(mut v: Vec<I>) -> Self {
+        v.sort_unstable();
+        v.dedup();
+        Self(Cow::Owned(v))
+    }
+}
+
+/// Deserialize a `Vec` of indices and checks, whether the indices are sorted and unique.
+#[cfg(feature = "serde")]
+fn deserialize_sorted<'de, D, X>(deserializer: D) -> Result<Vec<X>, D::Error>
+where
+    D: Deserializer<'de>,
+    X: Deserialize<'de> + Ord,
+{
+    let v = Vec::<X>::deserialize(deserializer)?;
+    if v.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(de::Error::custom("the indices are not sorted and unique"));
+    }
+    Ok(v)
+}
+
+#[cfg(feature = "serde")]
+impl<X: Serialize> Serialize for UniqueKeyIndex<X> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0
+            .as_ref()
+            .map_or([].as_slice(), |idx| idx.as_slice())
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, X: Deserialize<'de>> Deserialize<'de> for UniqueKeyIndex<X> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut v = Vec::<X>::deserialize(deserializer)?;
+        match (v.pop(), v.is_empty()) {
+            (Some(idx), true) => Ok(Self(Some([idx]))),
+            _ => Err(de::Error::custom(
+                "a unique index must have exactly one index",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<X: Serialize> Serialize for MultiKeyIndex<X> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, X: Deserialize<'de> + Ord> Deserialize<'de> for MultiKeyIndex<X> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let v = deserialize_sorted(deserializer)?;
+        if v.is_empty() {
+            return Err(de::Error::custom(
+                "a multi index must have at least one index",
+            ));
+        }
+        Ok(Self(v))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: Serialize + Clone> Serialize for Indices<'_, I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I: Deserialize<'de> + Ord + Clone> Deserialize<'de> for Indices<'_, I> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self(Cow::Owned(deserialize_sorted(deserializer)?)))
+    }
+}
+
 impl<I: PartialEq + Clone, const N: usize> PartialEq<Indices<'_, I>> for [I; N] {
     fn eq(&self, other: &Indices<'_, I>) -> bool {
         (self).eq(&*other.0)
@@ -200,6 +367,24 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case::first(0, 2, vec![1, 3])]
+    #[case::middle(1, 2, vec![3, 5])]
+    #[case::end(2, 5, vec![5])]
+    #[case::after_end(10, 5, vec![])]
+    #[case::no_limit(1, 0, vec![])]
+    fn page(#[case] offset: usize, #[case] limit: usize, #[case] expected: Vec<usize>) {
+        let idxs = [1, 3, 5];
+        assert_eq!(
+            expected,
+            Indices::borrowed(&idxs).page(offset, limit).as_slice()
+        );
+        assert_eq!(
+            expected,
+            Indices::owned(idxs.to_vec()).page(offset, limit).as_slice()
+        );
+    }
+
     mod unique_key_indices {
         use super::*;
 
@@ -513,6 +698,12 @@ mod tests {
             assert_eq!([0, 3], l.eq(0) | l.eq(1) & l.eq(2) | l.eq(3));
         }
 
+        #[test]
+        fn from_vec() {
+            let idxs: Indices = vec![3, 1, 2, 3, 1].into();
+            assert_eq!([1, 2, 3], idxs);
+        }
+
         #[test]
         fn iter() {
             let idxs = Indices::owned(vec![1, 3, 2]);
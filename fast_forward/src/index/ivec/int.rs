@@ -1,15 +1,18 @@
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData, ops::RangeBounds};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::index::{
     indices::{KeyIndex, MultiKeyIndex, UniqueKeyIndex},
-    ivec::IVec,
-    store::{Filterable, MetaData, Store, View, ViewCreator},
+    ivec::{int_bounds, IVec},
+    store::{Filterable, Merge, MetaData, Ordered, Positions, Store, View, ViewCreator},
 };
 
 pub type UniqueIntIndex<K = i32, X = usize> = IntIndex<UniqueKeyIndex<X>, K, X>;
 pub type MultiIntIndex<K = i32, X = usize> = IntIndex<MultiKeyIndex<X>, K, X>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(transparent)]
 pub struct IntIndex<I, K = i32, X = usize> {
     vec: IVec<I, K, X, (Option<I>, Option<I>)>,
@@ -33,6 +36,20 @@ where
     }
 }
 
+impl<I, K, X> Ordered for IntIndex<I, K, X>
+where
+    I: KeyIndex<X>,
+    K: Into<i32> + Copy,
+{
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>,
+    {
+        let (lo, hi) = int_bounds(&range, |k| (*k).into());
+        self.vec.range_signed(lo, hi)
+    }
+}
+
 impl<'a, I, K, X> ViewCreator<'a> for IntIndex<I, K, X>
 where
     I: KeyIndex<X> + 'a,
@@ -70,6 +87,8 @@ where
     I: KeyIndex<X> + Clone,
     K: Into<i32> + Copy,
 {
+    const UNIQUE: bool = I::UNIQUE;
+
     fn insert(&mut self, key: Self::Key, idx: Self::Index) {
         self.vec.insert(key.into(), idx)
     }
@@ -85,6 +104,53 @@ where
         }
     }
 }
+
+impl<I, K, X> Merge for IntIndex<I, K, X>
+where
+    I: KeyIndex<X> + Clone,
+    X: Clone,
+{
+    fn merge(&mut self, other: Self) {
+        self.vec.merge(other.vec)
+    }
+}
+
+impl<I, K> Positions for IntIndex<I, K, usize>
+where
+    I: KeyIndex<usize>,
+{
+    fn max_position(&self) -> Option<usize> {
+        self.vec.max_position()
+    }
+
+    fn count_positions(&self) -> usize {
+        self.vec.count_positions()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I, K, X> Serialize for IntIndex<I, K, X>
+where
+    I: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.vec.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, K, X> Deserialize<'de> for IntIndex<I, K, X>
+where
+    I: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            vec: IVec::deserialize(deserializer)?,
+            _key: PhantomData,
+        })
+    }
+}
+
 impl<I, K, X> Default for IntIndex<I, K, X>
 where
     I: KeyIndex<X>,
@@ -98,7 +164,12 @@ where
 }
 
 impl<I, K, X> MetaData for IntIndex<I, K, X> {
-    type Meta<'m> = IntMeta<'m,I, K, X> where I: 'm, K:'m,X:'m;
+    type Meta<'m>
+        = IntMeta<'m, I, K, X>
+    where
+        I: 'm,
+        K: 'm,
+        X: 'm;
 
     fn meta(&self) -> Self::Meta<'_> {
         IntMeta(&self.vec)
@@ -148,6 +219,7 @@ where
 mod tests {
     use super::*;
     use crate::index::filter::Filter;
+    use std::ops::Bound;
 
     #[test]
     fn insert_plus() {
@@ -255,6 +327,44 @@ mod tests {
         assert_eq!(view.get(&4), &[5, 8, 9, 99]);
     }
 
+    #[test]
+    fn range() {
+        let mut i = MultiIntIndex::<i8, u8>::default();
+        i.insert(-3, 1);
+        i.insert(-1, 2);
+        i.insert(0, 3);
+        i.insert(2, 4);
+        i.insert(2, 5);
+        i.insert(5, 6);
+
+        let r = |r: (Bound<i8>, Bound<i8>)| i.range(r).flatten().copied().collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![1, 2, 3, 4, 5, 6],
+            r((Bound::Unbounded, Bound::Unbounded))
+        );
+        assert_eq!(vec![1, 2, 3], r((Bound::Unbounded, Bound::Excluded(2))));
+        assert_eq!(
+            vec![2, 3, 4, 5],
+            r((Bound::Included(-2), Bound::Included(2)))
+        );
+        assert_eq!(vec![4, 5, 6], r((Bound::Excluded(0), Bound::Unbounded)));
+        assert_eq!(vec![1], r((Bound::Unbounded, Bound::Included(-2))));
+        assert_eq!(
+            vec![6, 4, 5, 3, 2, 1],
+            i.range(..).rev().flatten().copied().collect::<Vec<_>>()
+        );
+
+        // empty and out of bound ranges
+        assert!(r((Bound::Included(-100), Bound::Excluded(-3))).is_empty());
+        assert!(r((Bound::Included(6), Bound::Included(100))).is_empty());
+        assert!(r((Bound::Included(3), Bound::Included(-3))).is_empty());
+        assert!(MultiIntIndex::<i8, u8>::default()
+            .range(..)
+            .next()
+            .is_none());
+    }
+
     #[test]
     fn create_view_range() {
         let mut i = UniqueIntIndex::<i8, u8>::default();
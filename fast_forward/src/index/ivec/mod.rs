@@ -3,19 +3,25 @@
 use std::{
     fmt::Debug,
     marker::PhantomData,
-    ops::{Deref, DerefMut},
+    ops::{Bound, Deref, DerefMut, RangeBounds},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 use self::options::{KeyIndexOptionRead, KeyIndexOptionWrite};
 
-use super::{indices::KeyIndex, store::Filterable};
+use super::{
+    indices::KeyIndex,
+    store::{Filterable, Ordered},
+};
 
 pub mod int;
 mod new_filter;
 mod options;
 pub mod uint;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(transparent)]
 pub struct IVec<I, K, X, Opt> {
     vec: Vec<Opt>,
@@ -54,7 +60,7 @@ where
         let key = key.into();
         self.vec
             .get(key.value)
-            .map_or(false, |o| o.contains(key.is_negative))
+            .is_some_and(|o| o.contains(key.is_negative))
     }
 
     #[inline]
@@ -91,6 +97,175 @@ where
             rm_idx.delete(key.is_negative, index)
         }
     }
+
+    pub(crate) fn merge(&mut self, other: Self)
+    where
+        Opt: KeyIndexOptionWrite<I, X>,
+        X: Clone,
+    {
+        if self.vec.len() < other.vec.len() {
+            self.vec.resize(other.vec.len(), Opt::default());
+        }
+        self.vec
+            .iter_mut()
+            .zip(other.vec)
+            .for_each(|(o, other)| o.merge(other));
+    }
+
+    pub(crate) fn max_position(&self) -> Option<usize>
+    where
+        Opt: KeyIndexOptionRead<I, X>,
+        X: Ord + Copy + Into<usize>,
+    {
+        self.vec
+            .iter()
+            .flat_map(|o| [o.get(true).last(), o.get(false).last()])
+            .flatten()
+            .max()
+            .map(|x| (*x).into())
+    }
+
+    pub(crate) fn count_positions(&self) -> usize
+    where
+        Opt: KeyIndexOptionRead<I, X>,
+    {
+        self.vec.iter().map(|o| o.count()).sum()
+    }
+
+    /// The `Indices` for all positive `Keys` in: `start..end`, in ascending `Key` order.
+    pub(crate) fn range_positive(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl DoubleEndedIterator<Item = &[X]>
+    where
+        Opt: KeyIndexOptionRead<I, X>,
+    {
+        let end = end.min(self.vec.len());
+        self.vec[start.min(end)..end]
+            .iter()
+            .map(|o| o.get(false))
+            .filter(|i| !i.is_empty())
+    }
+
+    /// The `Indices` for all `Keys` in: `lo..=hi`, in ascending `Key` order.
+    pub(crate) fn range_signed(&self, lo: i64, hi: i64) -> impl DoubleEndedIterator<Item = &[X]>
+    where
+        Opt: KeyIndexOptionRead<I, X>,
+    {
+        // negative Keys are saved by the absolute value: -1 => 1, -2 => 2, ...
+        // so the ascending Key order is the descending position order in the Vec
+        let max = self.vec.len() as i64 - 1;
+        let neg = (
+            hi.min(-1).saturating_neg().max(1),
+            lo.saturating_neg().min(max),
+        );
+        let pos = (lo.max(0), hi.min(max));
+        let slice = |(from, to): (i64, i64)| {
+            if from > to {
+                &self.vec[0..0]
+            } else {
+                &self.vec[from as usize..=to as usize]
+            }
+        };
+        slice(neg)
+            .iter()
+            .rev()
+            .map(|o| o.get(true))
+            .chain(slice(pos).iter().map(|o| o.get(false)))
+            .filter(|i| !i.is_empty())
+    }
+}
+
+impl<I, X, Opt> Ordered for IVec<I, usize, X, Opt>
+where
+    I: KeyIndex<X>,
+    Opt: KeyIndexOptionRead<I, X>,
+{
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>,
+    {
+        let (start, end) = uint_bounds(&range, |k| *k);
+        self.range_positive(start, end)
+    }
+}
+
+impl<I, X, Opt> Ordered for IVec<I, i32, X, Opt>
+where
+    I: KeyIndex<X>,
+    Opt: KeyIndexOptionRead<I, X>,
+{
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>,
+    {
+        let (lo, hi) = int_bounds(&range, |k| *k);
+        self.range_signed(lo, hi)
+    }
+}
+
+/// Convert the bounds of the `range` into: `start..end` (`end` is `usize::MAX` for unbounded).
+pub(crate) fn uint_bounds<K, R, F>(range: &R, into: F) -> (usize, usize)
+where
+    R: RangeBounds<K>,
+    F: Fn(&K) -> usize,
+{
+    let start = match range.start_bound() {
+        Bound::Included(k) => into(k),
+        Bound::Excluded(k) => into(k).saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(k) => into(k).saturating_add(1),
+        Bound::Excluded(k) => into(k),
+        Bound::Unbounded => usize::MAX,
+    };
+    (start, end)
+}
+
+/// Convert the bounds of the `range` into the inclusive bounds: `lo..=hi`.
+pub(crate) fn int_bounds<K, R, F>(range: &R, into: F) -> (i64, i64)
+where
+    R: RangeBounds<K>,
+    F: Fn(&K) -> i32,
+{
+    let lo = match range.start_bound() {
+        Bound::Included(k) => i64::from(into(k)),
+        Bound::Excluded(k) => i64::from(into(k)) + 1,
+        Bound::Unbounded => i64::MIN,
+    };
+    let hi = match range.end_bound() {
+        Bound::Included(k) => i64::from(into(k)),
+        Bound::Excluded(k) => i64::from(into(k)) - 1,
+        Bound::Unbounded => i64::MAX,
+    };
+    (lo, hi)
+}
+
+#[cfg(feature = "serde")]
+impl<I, K, X, Opt> Serialize for IVec<I, K, X, Opt>
+where
+    Opt: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.vec.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, K, X, Opt> Deserialize<'de> for IVec<I, K, X, Opt>
+where
+    Opt: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            vec: Vec::deserialize(deserializer)?,
+            _key: PhantomData,
+            _index: PhantomData,
+            _key_index: PhantomData,
+        })
+    }
 }
 
 impl<I, K, X, Opt> Deref for IVec<I, K, X, Opt> {
@@ -81,7 +81,7 @@ where
     }
 }
 
-impl<'a, I, K, X> Filterable for Vec<Option<(&'a I, PhantomData<K>, PhantomData<X>)>>
+impl<I, K, X> Filterable for Vec<Option<(&I, PhantomData<K>, PhantomData<X>)>>
 where
     K: Into<usize>,
     I: KeyIndex<X>,
@@ -9,6 +9,8 @@ where
 {
     fn contains(&self, is_negativ: bool) -> bool;
     fn get(&self, is_negativ: bool) -> &[X];
+    /// The number of all `Indices` (negative and positive).
+    fn count(&self) -> usize;
 }
 
 impl<I, X> KeyIndexOptionRead<I, X> for Option<I>
@@ -22,6 +24,10 @@ where
     fn get(&self, _: bool) -> &[X] {
         self.as_ref().map_or(&[], |i| i.as_slice())
     }
+
+    fn count(&self) -> usize {
+        self.get(false).len()
+    }
 }
 
 impl<I, X> KeyIndexOptionRead<I, X> for Option<&I>
@@ -35,6 +41,10 @@ where
     fn get(&self, _: bool) -> &[X] {
         self.as_ref().map_or(&[], |i| (*i).as_slice())
     }
+
+    fn count(&self) -> usize {
+        self.get(false).len()
+    }
 }
 
 impl<I, X> KeyIndexOptionRead<I, X> for (Option<I>, Option<I>)
@@ -48,6 +58,10 @@ where
     fn get(&self, is_negativ: bool) -> &[X] {
         if is_negativ { &self.0 } else { &self.1 }.get(is_negativ)
     }
+
+    fn count(&self) -> usize {
+        self.get(true).len() + self.get(false).len()
+    }
 }
 
 impl<I, X> KeyIndexOptionRead<I, X> for (Option<&I>, Option<&I>)
@@ -63,6 +77,10 @@ where
             .as_ref()
             .map_or(&[], |i| (*i).as_slice())
     }
+
+    fn count(&self) -> usize {
+        self.get(true).len() + self.get(false).len()
+    }
 }
 
 // -------------
@@ -74,6 +92,9 @@ where
 {
     fn set(&mut self, is_negativ: bool, index: X);
     fn delete(&mut self, is_negativ: bool, index: &X);
+    fn merge(&mut self, other: Self)
+    where
+        X: Clone;
 }
 
 impl<I, X> KeyIndexOptionWrite<I, X> for Option<I>
@@ -94,6 +115,17 @@ where
             }
         }
     }
+
+    fn merge(&mut self, other: Self)
+    where
+        X: Clone,
+    {
+        match (self, other) {
+            (_, None) => {}
+            (Some(idx), Some(other)) => idx.merge(other),
+            (this, other) => *this = other,
+        }
+    }
 }
 
 impl<I, X> KeyIndexOptionWrite<I, X> for (Option<I>, Option<I>)
@@ -107,4 +139,12 @@ where
     fn delete(&mut self, is_negativ: bool, index: &X) {
         if is_negativ { &mut self.0 } else { &mut self.1 }.delete(is_negativ, index);
     }
+
+    fn merge(&mut self, other: Self)
+    where
+        X: Clone,
+    {
+        self.0.merge(other.0);
+        self.1.merge(other.1);
+    }
 }
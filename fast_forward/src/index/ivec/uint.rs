@@ -1,18 +1,21 @@
 //! This `Index` is well suitable for `IDs` with [`usize`] compatible data types (for example `Primary Keys`).
 //!
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData, ops::RangeBounds};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::index::{
     indices::{KeyIndex, MultiKeyIndex, UniqueKeyIndex},
-    ivec::IVec,
-    store::{Filterable, MetaData, Store, View, ViewCreator},
+    ivec::{uint_bounds, IVec},
+    store::{Filterable, Merge, MetaData, Ordered, Positions, Store, View, ViewCreator},
 };
 
 pub type UniqueUIntIndex<K = usize, X = usize> = UIntIndex<UniqueKeyIndex<X>, K, X>;
 pub type MultiUIntIndex<K = usize, X = usize> = UIntIndex<MultiKeyIndex<X>, K, X>;
 
 /// `Key` is from type [`usize`] and the information are saved in a List (Store).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(transparent)]
 pub struct UIntIndex<I, K = usize, X = usize> {
     vec: IVec<I, K, X, Option<I>>,
@@ -36,6 +39,20 @@ where
     }
 }
 
+impl<I, K, X> Ordered for UIntIndex<I, K, X>
+where
+    I: KeyIndex<X>,
+    K: Into<usize> + Copy,
+{
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>,
+    {
+        let (start, end) = uint_bounds(&range, |k| (*k).into());
+        self.vec.range_positive(start, end)
+    }
+}
+
 impl<'a, I, K, X> ViewCreator<'a> for UIntIndex<I, K, X>
 where
     I: KeyIndex<X> + 'a,
@@ -67,6 +84,8 @@ where
     I: KeyIndex<X> + Clone,
     K: Into<usize> + Copy,
 {
+    const UNIQUE: bool = I::UNIQUE;
+
     fn insert(&mut self, key: Self::Key, idx: Self::Index) {
         self.vec.insert(key.into(), idx)
     }
@@ -83,6 +102,52 @@ where
     }
 }
 
+impl<I, K, X> Merge for UIntIndex<I, K, X>
+where
+    I: KeyIndex<X> + Clone,
+    X: Clone,
+{
+    fn merge(&mut self, other: Self) {
+        self.vec.merge(other.vec)
+    }
+}
+
+impl<I, K> Positions for UIntIndex<I, K, usize>
+where
+    I: KeyIndex<usize>,
+{
+    fn max_position(&self) -> Option<usize> {
+        self.vec.max_position()
+    }
+
+    fn count_positions(&self) -> usize {
+        self.vec.count_positions()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I, K, X> Serialize for UIntIndex<I, K, X>
+where
+    I: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.vec.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, K, X> Deserialize<'de> for UIntIndex<I, K, X>
+where
+    I: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            vec: IVec::deserialize(deserializer)?,
+            _key: PhantomData,
+        })
+    }
+}
+
 impl<I, K, X> Default for UIntIndex<I, K, X>
 where
     I: KeyIndex<X>,
@@ -96,7 +161,12 @@ where
 }
 
 impl<I, K, X> MetaData for UIntIndex<I, K, X> {
-    type Meta<'m> = UIntMeta<'m, I,K,X> where I:'m,K:'m,X:'m;
+    type Meta<'m>
+        = UIntMeta<'m, I, K, X>
+    where
+        I: 'm,
+        K: 'm,
+        X: 'm;
 
     fn meta(&self) -> Self::Meta<'_> {
         UIntMeta(&self.vec)
@@ -136,6 +206,7 @@ mod tests {
         },
         *,
     };
+    use std::ops::Bound;
 
     impl UIntIndex<MultiKeyIndex<usize>, usize, usize> {
         fn new() -> Self {
@@ -178,6 +249,43 @@ mod tests {
         assert_eq!(view.get(&4), &[5, 8, 9, 99]);
     }
 
+    #[test]
+    fn range() {
+        let mut i = MultiUIntIndex::<u8, u8>::default();
+        i.insert(1, 2);
+        i.insert(2, 4);
+        i.insert(2, 5);
+        i.insert(4, 8);
+        i.insert(9, 10);
+
+        let r = |i: &MultiUIntIndex<u8, u8>, r: (Bound<u8>, Bound<u8>)| {
+            i.range(r).flatten().copied().collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![4, 5, 8],
+            i.range(2..5).flatten().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![2, 4, 5],
+            i.range(..=2).flatten().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![8, 10],
+            i.range(3..).flatten().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![10], r(&i, (Bound::Excluded(4), Bound::Unbounded)));
+        assert_eq!(
+            vec![10, 8, 4, 5, 2],
+            i.range(..).rev().flatten().copied().collect::<Vec<_>>()
+        );
+
+        // empty and out of bound ranges
+        assert!(i.range(5..9).next().is_none());
+        assert!(i.range(100..).next().is_none());
+        assert!(r(&i, (Bound::Included(4), Bound::Excluded(2))).is_empty());
+    }
+
     #[test]
     fn retrieve() {
         let mut i = UIntIndex::new();
@@ -1,10 +1,14 @@
 //! The `index `module contains the structure for saving and accessing the `Index` implementations.
+pub mod ibtree;
 pub mod imap;
 pub mod indices;
 pub mod ivec;
 pub mod ops;
+#[cfg(feature = "rayon")]
+pub mod par;
 pub mod store;
 
+pub use ibtree::SortedIndex;
 pub use imap::MapIndex;
 pub use ivec::int::{MultiIntIndex, UniqueIntIndex};
 pub use ivec::uint::{MultiUIntIndex, UniqueUIntIndex};
@@ -65,7 +69,7 @@ pub trait Indexable<Idx> {
 
     /// Return an `Iterator` with all `Items`
     /// for a given `Iterator` with `Indices`.
-    fn items<'a, I>(&'a self, indices: I) -> Items<Self, Idx, I>
+    fn items<'a, I>(&'a self, indices: I) -> Items<'a, Self, Idx, I>
     where
         I: Iterator<Item = &'a Idx>,
         Self: Sized,
@@ -117,7 +121,7 @@ macro_rules! list_indexable {
     };
 }
 
-list_indexable!(Vec<T>, std::collections::VecDeque<T>, &[T]);
+list_indexable!(Vec<T>, std::collections::VecDeque<T>, &[T], [T]);
 
 impl<T, const N: usize> Indexable<usize> for [T; N] {
     type Output = T;
@@ -0,0 +1,117 @@
+//! Create `Stores` in parallel with [`rayon`] (feature: `rayon`).
+//!
+//! The `Items` are partitioned, for every part a partial `Store` is created (in parallel)
+//! and the partial `Stores` are merged in order (see: [`Merge`]).
+//! So the positions in the merged `Store` are sorted, like by a sequential insert.
+//!
+use rayon::prelude::*;
+
+use crate::index::store::{Indexer, Merge};
+
+/// The minimum number of `Items` for one part, smaller parts are not worth a thread.
+const MIN_PART_LEN: usize = 4096;
+
+/// Create the `Store(s)` for the given `Items` in parallel.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::index::{par, MapIndex, store::Filterable};
+///
+/// let items = vec![1, 2, 1];
+/// let s: MapIndex<i32> = par::to_indexer(&items, &|i: &i32| *i);
+///
+/// assert_eq!(&[0, 2], s.get(&1));
+/// ```
+pub fn to_indexer<S, I, F>(items: &[I], field: &F) -> S
+where
+    S: Indexer<I, F, Index = usize> + Merge + Send,
+    I: Sync,
+    F: Sync,
+{
+    let part_len = (items.len() / rayon::current_num_threads()).max(MIN_PART_LEN);
+    to_indexer_by_parts(items, field, part_len)
+}
+
+fn to_indexer_by_parts<S, I, F>(items: &[I], field: &F, part_len: usize) -> S
+where
+    S: Indexer<I, F, Index = usize> + Merge + Send,
+    I: Sync,
+    F: Sync,
+{
+    items
+        .par_chunks(part_len)
+        .enumerate()
+        .map(|(n, part)| {
+            let start = n * part_len;
+            let mut s = S::with_capacity(part.len());
+            part.iter()
+                .enumerate()
+                .for_each(|(idx, item)| s.insert_item(field, item, start + idx));
+            s
+        })
+        // reduce keeps the order of the parts
+        .reduce_with(|mut s, other| {
+            s.merge(other);
+            s
+        })
+        .unwrap_or_else(|| S::with_capacity(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::{
+        store::{Filterable, ToIndexer},
+        MapIndex, MultiIntIndex, SortedIndex, UniqueUIntIndex,
+    };
+    use rstest::rstest;
+
+    #[derive(Debug, PartialEq)]
+    struct Car(usize, i32, String);
+
+    fn cars() -> Vec<Car> {
+        (0..100)
+            .map(|i| Car(i, i as i32 % 7 - 3, format!("car-{}", i % 13)))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::one_item_per_part(1)]
+    #[case::small_parts(3)]
+    #[case::one_part(1000)]
+    fn same_as_sequential(#[case] part_len: usize) {
+        let cars = cars();
+        let fields = (
+            |c: &Car| c.0,
+            |c: &Car| c.1,
+            |c: &Car| c.2.clone(),
+            |c: &Car| c.2.clone(),
+        );
+        type S = (UniqueUIntIndex, MultiIntIndex, MapIndex, SortedIndex);
+
+        let (ids, ints, names, sorted): S = to_indexer_by_parts(&cars, &fields, part_len);
+        let expected: S = cars.to_indexer(&fields);
+
+        for c in &cars {
+            assert_eq!(expected.0.get(&c.0), ids.get(&c.0));
+            assert_eq!(expected.1.get(&c.1), ints.get(&c.1));
+            assert_eq!(expected.2.get(&c.2), names.get(&c.2));
+            assert_eq!(expected.3.get(&c.2), sorted.get(&c.2));
+        }
+        assert_eq!(&[0, 13, 26, 39, 52, 65, 78, 91], names.get(&"car-0".into()));
+        assert_eq!(&[3, 10, 17], &ints.get(&0)[..3]);
+    }
+
+    #[test]
+    fn empty() {
+        let s: MapIndex = to_indexer(&Vec::<Car>::new(), &|c: &Car| c.2.clone());
+        assert!(!s.contains(&"car-0".into()));
+    }
+
+    #[test]
+    #[should_panic(expected = "unique index can not add a new index")]
+    fn unique_key_in_two_parts() {
+        let _: UniqueUIntIndex = to_indexer_by_parts(&[1usize, 2, 1], &|i: &usize| *i, 2);
+    }
+}
@@ -1,10 +1,15 @@
 //! A `Store` is saving `Indices` for a given `Key`,
 //! with the goal, to get the `Indices` as fast as possible.
 
+use std::ops::RangeBounds;
+
 use crate::index::Indexable;
 
 /// A Store is a mapping from a given `Key` to one or many `Indices`.
 pub trait Store: Filterable {
+    /// `true`, if a `Key` has maximal one `Index` (a unique `Store`).
+    const UNIQUE: bool = false;
+
     /// Insert an `Key` for a given `Index`.
     ///
     /// Before:
@@ -111,6 +116,103 @@ pub trait Store: Filterable {
     }
 }
 
+/// Merge two `Stores` (or tuple of `Stores`), e.g. partial `Stores`, which are created in parallel.
+/// If all `Indices` of the other `Store` are greater, they are appended (fast), otherwise inserted.
+pub trait Merge {
+    /// Add all `Keys` and `Indices` of the other `Store`.
+    fn merge(&mut self, other: Self);
+}
+
+/// The greatest `Index` (position in a List) of a `Store` (or tuple of `Stores`),
+/// e.g. to check, whether all positions exist in the List.
+pub trait Positions {
+    fn max_position(&self) -> Option<usize>;
+
+    /// The number of all positions (of all `Keys` and all `Stores`).
+    fn count_positions(&self) -> usize;
+
+    /// Checks, whether all positions are less than the given length of the List.
+    // `Option::is_none_or` needs Rust 1.82
+    #[allow(clippy::unnecessary_map_or)]
+    fn check_positions(&self, len: usize) -> bool {
+        self.max_position().map_or(true, |max| max < len)
+    }
+}
+
+/// Checks the positions of a `Store` (or of every `Store` of a tuple) against the `Items`,
+/// e.g. after a deserialization.
+pub trait Validate<I, F>: Indexer<I, F, Index = usize> + Positions {
+    /// Checks, whether every `Store` contains the `Key` of the `Item` for the given position.
+    fn contains_item(&self, field: &F, item: &I, pos: usize) -> bool;
+
+    /// Checks, whether every `Store` contains exactly `len` positions.
+    fn check_count(&self, len: usize) -> bool;
+}
+
+impl<S, I, F> Validate<I, F> for S
+where
+    S: Store<Index = usize> + Positions,
+    F: Fn(&I) -> S::Key,
+{
+    fn contains_item(&self, field: &F, item: &I, pos: usize) -> bool {
+        self.get(&field(item)).contains(&pos)
+    }
+
+    fn check_count(&self, len: usize) -> bool {
+        self.count_positions() == len
+    }
+}
+
+/// Validate the unique `Stores` (see: [`Store::UNIQUE`]) of an [`Indexer`], before changing the `Items`.
+pub trait Unique<I, F>: Indexer<I, F> {
+    /// Checks, whether every `Key` of the `changed` `Items` is unique in every unique `Store`.
+    /// `unchanged` returns `true`, if the `Item` of the given `Index` is neither updated nor removed.
+    fn check_unique<'i, It, U>(&self, field: &F, changed: It, unchanged: U) -> bool
+    where
+        I: 'i,
+        It: Iterator<Item = (Self::Index, &'i I)> + Clone,
+        U: Fn(&Self::Index) -> bool;
+}
+
+fn check_unique<S, It, U>(store: &S, changed: It, unchanged: &U) -> bool
+where
+    S: Store,
+    It: Iterator<Item = (S::Key, S::Index)>,
+    U: Fn(&S::Index) -> bool,
+{
+    if !S::UNIQUE {
+        return true;
+    }
+
+    let mut keys = S::with_capacity(0);
+    for (key, idx) in changed {
+        if keys.contains(&key) || store.get(&key).iter().any(unchanged) {
+            return false;
+        }
+        keys.insert(key, idx);
+    }
+    true
+}
+
+impl<S, I, F> Unique<I, F> for S
+where
+    S: Store,
+    F: Fn(&I) -> S::Key,
+{
+    fn check_unique<'i, It, U>(&self, field: &F, changed: It, unchanged: U) -> bool
+    where
+        I: 'i,
+        It: Iterator<Item = (Self::Index, &'i I)> + Clone,
+        U: Fn(&Self::Index) -> bool,
+    {
+        check_unique(
+            self,
+            changed.map(|(idx, item)| (field(item), idx)),
+            &unchanged,
+        )
+    }
+}
+
 /// Returns a list to the indices [`crate::index::indices::Indices`] corresponding to the key.
 pub trait Filterable {
     type Key;
@@ -168,6 +270,31 @@ impl<F: Filterable> Filterable for View<F> {
     }
 }
 
+impl<F: Ordered> Ordered for View<F> {
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>,
+    {
+        self.0.range(range)
+    }
+}
+
+/// A [`Filterable`], where the `Keys` are saved in an ascending order.
+/// So it is possible to get all `Indices` for a `Key-Range`.
+pub trait Ordered: Filterable {
+    /// Returns the `Indices` for all `Keys` in the given `range`, grouped by `Key`
+    /// and in ascending `Key` order (use `rev()` for a descending order).
+    ///
+    /// # Example:
+    ///```text
+    /// range(2..5) => get(2), get(3), get(4)
+    /// range(..=1) => get(MIN), ..., get(0), get(1)
+    /// ```
+    fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = &[Self::Index]>
+    where
+        R: RangeBounds<Self::Key>;
+}
+
 /// Meta data from the [`Store`], like min or max value of the `Key`.
 pub trait MetaData {
     type Meta<'m>
@@ -250,6 +377,214 @@ where
     }
 }
 
+/// An `Indexer` is a [`Store`] or a tuple of `Stores` (max. 4), with the function(s) `F`
+/// for mapping an `Item` to the `Key(s)`: `(S1, S2)` with `(Fn(&I) -> S1::Key, Fn(&I) -> S2::Key)`.
+/// All `Stores` are changed together, so they are always in sync.
+///
+/// The first `Store` is the primary `Store` (e.g. for an `upsert`).
+///
+/// ## Hint:
+/// The `Key` of the primary `Store` identifies one `Item` for `upsert`, `update_by_primary`
+/// and `remove_by_primary`.
+/// So the primary `Store` must be a unique `Store` (e.g. [`crate::index::UniqueUIntIndex`]),
+/// with a multi `Store` these operations panic in debug builds, if a `Key` has more than one `Item`.
+pub trait Indexer<I, F> {
+    type Index;
+    /// The `Key(s)` of one `Item`, one `Key` for every `Store`.
+    type Keys;
+    /// The `Key` of the primary (first) `Store`.
+    type PrimaryKey;
+
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Returns the `Key` of the `Item` for the primary `Store`.
+    fn primary_key(field: &F, item: &I) -> Self::PrimaryKey;
+
+    /// Get all `Indices` for the given `Key` from the primary `Store`,
+    /// for a unique primary `Store` this is maximal one `Index`.
+    fn primary(&self, key: &Self::PrimaryKey) -> &[Self::Index];
+
+    /// Returns the current `Key(s)` of the `Item`, e.g. before an update.
+    fn keys(field: &F, item: &I) -> Self::Keys;
+
+    /// Insert the `Key(s)` of the `Item` for the given `Index`.
+    fn insert_item(&mut self, field: &F, item: &I, idx: Self::Index);
+
+    /// Delete the `Key(s)` of the `Item` for the given `Index`.
+    fn delete_item(&mut self, field: &F, item: &I, idx: &Self::Index);
+
+    /// Update the `Key(s)` from the given old `Key(s)` to the `Key(s)` of the (updated) `Item`.
+    fn update_item(&mut self, field: &F, old_keys: Self::Keys, item: &I, idx: Self::Index);
+}
+
+impl<S, I, F> Indexer<I, F> for S
+where
+    S: Store,
+    F: Fn(&I) -> S::Key,
+{
+    type Index = S::Index;
+    type Keys = S::Key;
+    type PrimaryKey = S::Key;
+
+    fn with_capacity(capacity: usize) -> Self {
+        <S as Store>::with_capacity(capacity)
+    }
+
+    fn primary_key(field: &F, item: &I) -> Self::PrimaryKey {
+        field(item)
+    }
+
+    fn primary(&self, key: &Self::PrimaryKey) -> &[Self::Index] {
+        self.get(key)
+    }
+
+    fn keys(field: &F, item: &I) -> Self::Keys {
+        field(item)
+    }
+
+    fn insert_item(&mut self, field: &F, item: &I, idx: Self::Index) {
+        self.insert(field(item), idx);
+    }
+
+    fn delete_item(&mut self, field: &F, item: &I, idx: &Self::Index) {
+        self.delete(field(item), idx);
+    }
+
+    fn update_item(&mut self, field: &F, old_keys: Self::Keys, item: &I, idx: Self::Index) {
+        self.update(old_keys, idx, field(item));
+    }
+}
+
+macro_rules! tuple_indexer {
+    ( $s0:ident $f0:ident 0 $( , $s:ident $f:ident $n:tt )+ ) => {
+        impl<$s0, $( $s, )+ I, $f0, $( $f, )+> Indexer<I, ($f0, $( $f, )+)> for ($s0, $( $s, )+)
+        where
+            $s0: Store,
+            $s0::Index: Clone,
+            $f0: Fn(&I) -> $s0::Key,
+            $(
+            $s: Store<Index = $s0::Index>,
+            $f: Fn(&I) -> $s::Key,
+            )+
+        {
+            type Index = $s0::Index;
+            type Keys = ($s0::Key, $( $s::Key, )+);
+            type PrimaryKey = $s0::Key;
+
+            fn with_capacity(capacity: usize) -> Self {
+                ($s0::with_capacity(capacity), $( $s::with_capacity(capacity), )+)
+            }
+
+            fn primary_key(field: &($f0, $( $f, )+), item: &I) -> Self::PrimaryKey {
+                (field.0)(item)
+            }
+
+            fn primary(&self, key: &Self::PrimaryKey) -> &[Self::Index] {
+                self.0.get(key)
+            }
+
+            fn keys(field: &($f0, $( $f, )+), item: &I) -> Self::Keys {
+                ((field.0)(item), $( (field.$n)(item), )+)
+            }
+
+            fn insert_item(&mut self, field: &($f0, $( $f, )+), item: &I, idx: Self::Index) {
+                $( self.$n.insert((field.$n)(item), idx.clone()); )+
+                self.0.insert((field.0)(item), idx);
+            }
+
+            fn delete_item(&mut self, field: &($f0, $( $f, )+), item: &I, idx: &Self::Index) {
+                self.0.delete((field.0)(item), idx);
+                $( self.$n.delete((field.$n)(item), idx); )+
+            }
+
+            fn update_item(
+                &mut self,
+                field: &($f0, $( $f, )+),
+                old_keys: Self::Keys,
+                item: &I,
+                idx: Self::Index,
+            ) {
+                $( self.$n.update(old_keys.$n, idx.clone(), (field.$n)(item)); )+
+                self.0.update(old_keys.0, idx, (field.0)(item));
+            }
+        }
+
+        impl<$s0, $( $s, )+> Merge for ($s0, $( $s, )+)
+        where
+            $s0: Merge,
+            $( $s: Merge, )+
+        {
+            fn merge(&mut self, other: Self) {
+                self.0.merge(other.0);
+                $( self.$n.merge(other.$n); )+
+            }
+        }
+
+        impl<$s0, $( $s, )+> Positions for ($s0, $( $s, )+)
+        where
+            $s0: Positions,
+            $( $s: Positions, )+
+        {
+            fn max_position(&self) -> Option<usize> {
+                self.0.max_position()$( .max(self.$n.max_position()) )+
+            }
+
+            fn count_positions(&self) -> usize {
+                self.0.count_positions() $( + self.$n.count_positions() )+
+            }
+        }
+
+        impl<$s0, $( $s, )+ I, $f0, $( $f, )+> Validate<I, ($f0, $( $f, )+)> for ($s0, $( $s, )+)
+        where
+            $s0: Store<Index = usize> + Positions,
+            $f0: Fn(&I) -> $s0::Key,
+            $(
+            $s: Store<Index = usize> + Positions,
+            $f: Fn(&I) -> $s::Key,
+            )+
+        {
+            fn contains_item(&self, field: &($f0, $( $f, )+), item: &I, pos: usize) -> bool {
+                self.0.get(&(field.0)(item)).contains(&pos)
+                    $( && self.$n.get(&(field.$n)(item)).contains(&pos) )+
+            }
+
+            fn check_count(&self, len: usize) -> bool {
+                self.0.count_positions() == len $( && self.$n.count_positions() == len )+
+            }
+        }
+
+        impl<$s0, $( $s, )+ I, $f0, $( $f, )+> Unique<I, ($f0, $( $f, )+)> for ($s0, $( $s, )+)
+        where
+            $s0: Store,
+            $s0::Index: Clone,
+            $f0: Fn(&I) -> $s0::Key,
+            $(
+            $s: Store<Index = $s0::Index>,
+            $f: Fn(&I) -> $s::Key,
+            )+
+        {
+            fn check_unique<'i, It, U>(
+                &self,
+                field: &($f0, $( $f, )+),
+                changed: It,
+                unchanged: U,
+            ) -> bool
+            where
+                I: 'i,
+                It: Iterator<Item = (Self::Index, &'i I)> + Clone,
+                U: Fn(&Self::Index) -> bool,
+            {
+                check_unique(&self.0, changed.clone().map(|(idx, item)| ((field.0)(item), idx)), &unchanged)
+                    $( && check_unique(&self.$n, changed.clone().map(|(idx, item)| ((field.$n)(item), idx)), &unchanged) )+
+            }
+        }
+    };
+}
+
+tuple_indexer!(S0 F0 0, S1 F1 1);
+tuple_indexer!(S0 F0 0, S1 F1 1, S2 F2 2);
+tuple_indexer!(S0 F0 0, S1 F1 1, S2 F2 2, S3 F3 3);
+
 /// Create a [`Store`] from a given List or Map and
 /// a function for mapping a Struct-Field to an Index.
 pub trait ToStore<X, T> {
@@ -260,71 +595,83 @@ pub trait ToStore<X, T> {
         F: FnMut(&T) -> S::Key;
 }
 
-impl<T, const N: usize> ToStore<usize, T> for [T; N] {
-    fn to_store<S, F>(&self, field: F) -> S
+/// Create an [`Indexer`] (one or many `Stores`) from a given List or Map.
+pub trait ToIndexer<X, T> {
+    /// Insert the Items from the collection into the [`Indexer`].
+    fn to_indexer<S, F>(&self, field: &F) -> S
     where
-        S: Store<Index = usize>,
-        F: FnMut(&T) -> <S>::Key,
-    {
-        S::from_list(self.iter().map(field))
-    }
+        S: Indexer<T, F, Index = X>;
 }
 
-impl<'a, T> ToStore<usize, T> for &'a [T] {
-    fn to_store<S, F>(&self, field: F) -> S
-    where
-        S: Store<Index = usize>,
-        F: FnMut(&T) -> <S>::Key,
-    {
-        S::from_list(self.iter().map(field))
-    }
-}
+macro_rules! list_to_store {
+    ( $( $t:ty $( ; const $n:ident )? ),* ) => {
+        $(
+        impl<T $( , const $n: usize )?> ToStore<usize, T> for $t {
+            fn to_store<S, F>(&self, field: F) -> S
+            where
+                S: Store<Index = usize>,
+                F: FnMut(&T) -> <S>::Key,
+            {
+                S::from_list(self.iter().map(field))
+            }
+        }
 
-impl<T> ToStore<usize, T> for Vec<T> {
-    fn to_store<S, F>(&self, field: F) -> S
-    where
-        S: Store<Index = usize>,
-        F: FnMut(&T) -> <S>::Key,
-    {
-        S::from_list(self.iter().map(field))
-    }
+        impl<T $( , const $n: usize )?> ToIndexer<usize, T> for $t {
+            fn to_indexer<S, F>(&self, field: &F) -> S
+            where
+                S: Indexer<T, F, Index = usize>,
+            {
+                let mut s = S::with_capacity(self.len());
+                self.iter()
+                    .enumerate()
+                    .for_each(|(idx, item)| s.insert_item(field, item, idx));
+                s
+            }
+        }
+        )*
+    };
 }
 
-impl<T> ToStore<usize, T> for std::collections::VecDeque<T> {
-    fn to_store<S, F>(&self, field: F) -> S
-    where
-        S: Store<Index = usize>,
-        F: FnMut(&T) -> <S>::Key,
-    {
-        S::from_list(self.iter().map(field))
-    }
-}
+list_to_store!([T; N]; const N, &[T], Vec<T>, std::collections::VecDeque<T>);
+
+macro_rules! map_to_store {
+    ( $( $t:ty ),* ) => {
+        $(
+        impl<X, T> ToStore<X, T> for $t
+        where
+            X: Clone,
+        {
+            fn to_store<S, F>(&self, mut field: F) -> S
+            where
+                S: Store<Index = X>,
+                F: FnMut(&T) -> <S>::Key,
+            {
+                S::from_map(self.iter().map(|(idx, item)| (field(item), idx.clone())))
+            }
+        }
 
-impl<X, T> ToStore<X, T> for std::collections::HashMap<X, T>
-where
-    X: Clone,
-{
-    fn to_store<S, F>(&self, mut field: F) -> S
-    where
-        S: Store<Index = X>,
-        F: FnMut(&T) -> <S>::Key,
-    {
-        S::from_map(self.iter().map(|(idx, item)| (field(item), idx.clone())))
-    }
+        impl<X, T> ToIndexer<X, T> for $t
+        where
+            X: Clone,
+        {
+            fn to_indexer<S, F>(&self, field: &F) -> S
+            where
+                S: Indexer<T, F, Index = X>,
+            {
+                let mut s = S::with_capacity(self.len());
+                self.iter()
+                    .for_each(|(idx, item)| s.insert_item(field, item, idx.clone()));
+                s
+            }
+        }
+        )*
+    };
 }
 
-impl<X, T> ToStore<X, T> for std::collections::BTreeMap<X, T>
-where
-    X: Clone,
-{
-    fn to_store<S, F>(&self, mut field: F) -> S
-    where
-        S: Store<Index = X>,
-        F: FnMut(&T) -> <S>::Key,
-    {
-        S::from_map(self.iter().map(|(idx, item)| (field(item), idx.clone())))
-    }
-}
+map_to_store!(
+    std::collections::HashMap<X, T>,
+    std::collections::BTreeMap<X, T>
+);
 
 #[cfg(test)]
 mod tests {
@@ -429,4 +776,32 @@ mod tests {
         let map = MapIndex::from_list(items.clone());
         assert_eq!(expected, map.get_many(keys).items_vec(&items));
     }
+
+    #[test]
+    fn tuple_indexer() {
+        let field = (
+            |s: &&str| s.len(),
+            |s: &&str| s.to_string(),
+            |s: &&str| s.chars().next().unwrap(),
+        );
+        let mut i = (
+            MapIndex::<usize>::from_map([].into_iter()),
+            MapIndex::<String>::from_map([].into_iter()),
+            MapIndex::<char>::from_map([].into_iter()),
+        );
+        i.insert_item(&field, &"abc", 0);
+        i.insert_item(&field, &"xy", 1);
+        assert_eq!([0], i.0.get(&3));
+        assert_eq!([1], i.1.get(&"xy".into()));
+
+        i.update_item(&field, (2, "xy".into(), 'x'), &"a", 1);
+        assert_eq!([1], i.0.get(&1));
+        assert!(!i.1.contains(&"xy".into()));
+        assert_eq!([0, 1], i.2.get(&'a'));
+
+        i.delete_item(&field, &"abc", &0);
+        assert!(!i.0.contains(&3));
+        assert!(!i.1.contains(&"abc".into()));
+        assert_eq!([1], i.2.get(&'a'));
+    }
 }
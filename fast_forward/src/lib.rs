@@ -88,9 +88,23 @@
 //! assert!(l.idx().contains(&String::from("VW")));
 //! ```
 //!
+//! ## Query language
+//!
+//! Queries like: `name = "BMW" AND (id IN [1, 2, 3] OR price < 100)` can be parsed and
+//! executed against many indices. All details to [`crate::query`].
+//!
+//! ## Features
+//!
+//! - `hashbrown`: the `MapIndex` and the `rw::IMap` use the `hashbrown::HashMap`
+//! - `rayon`: parallel queries and the opt-in parallel creation of the `Stores`
+//!   with the `par_*` constructors (e.g. `ro::IList::par_new`, `rw::IList::par_from_vec`).
+//!   The constructors `new` and `from_vec` create the `Stores` sequentially, also with this feature.
+//! - `serde`: `Serialize` and `Deserialize` for the `Stores` and the Lists
+//!
 
 pub mod collections;
 pub mod index;
+pub mod query;
 
 /// This `macro` is not a solution, it is more an POC (proof of concept)!
 /// The Problem with this macro is the visibility. This means, it can not hide internal fields,
@@ -133,7 +147,6 @@ macro_rules! fast {
             _items_: $crate::collections::rw::list_base::TriggerList<$item>,
         }
 
-        ///
         impl $fast {
 
             /// Insert the given item.
@@ -208,9 +221,8 @@ macro_rules! fast {
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        fast,
-        index::{filter::Filter, imap::MapIndex, ivec::uint::MultiUIntIndex, store::Filterable},
+    use crate::index::{
+        filter::Filter, imap::MapIndex, ivec::uint::MultiUIntIndex, store::Filterable,
     };
 
     #[derive(Debug, Eq, PartialEq)]
@@ -0,0 +1,630 @@
+//! A small textual query language, for querying a collection with many indices.
+//!
+//! ```text
+//! name = "BMW" AND (id IN [1, 2, 3] OR price < 100)
+//! ```
+//!
+//! Every `field` in the query is the name of an `Index`, which is registered in a [`Schema`].
+//! The `values` are converted into the `Key` type of the `Index` (see: [`FromValue`]).
+//!
+//! Supported are:
+//! - the operators: `=`, `IN [...]` and for ordered Indices (see: [`crate::index::store::Ordered`]): `<`, `<=`, `>`, `>=`
+//! - the combinations: `AND`, `OR` and parentheses, where `AND` binds stronger than `OR`
+//! - the values: integers (`-5`), strings (`"BMW"`) and bools (`true`, `false`)
+//!
+//! The query is executed by a [`Plan`], which orders the predicates by their selectivity
+//! (see: [`Query::explain`]). Fields without an `Index` can be registered with [`Schema::scan_field`].
+//!
+//! ## Example
+//!
+//! ```
+//! use fast_forward::{
+//!     collections::ro::IRefList,
+//!     index::{MapIndex, UniqueUIntIndex},
+//!     query::{Query, Schema},
+//! };
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Car(usize, String);
+//!
+//! let cars = vec![
+//!     Car(1, "BMW".into()),
+//!     Car(2, "VW".into()),
+//!     Car(3, "BMW".into()),
+//!     Car(4, "Audi".into()),
+//! ];
+//!
+//! let ids = IRefList::<UniqueUIntIndex, _>::new(|c| c.0, &cars);
+//! let names = IRefList::<MapIndex, _>::new(|c| c.1.clone(), &cars);
+//!
+//! let schema = Schema::new(&cars)
+//!     .ordered_field("id", ids.idx())
+//!     .field("name", names.idx());
+//!
+//! let q = Query::parse(r#"name = "BMW" AND (id IN [1, 2] OR id > 3)"#).unwrap();
+//! assert_eq!(
+//!     vec![&Car(1, "BMW".into())],
+//!     q.items(&schema).unwrap().collect::<Vec<_>>()
+//! );
+//!
+//! // a value, which can not convert into the Key type of the Index
+//! let err = schema.query(r#"id = "BMW""#).err().unwrap();
+//! assert_eq!(
+//!     r#"column 6: invalid value "BMW" for field `id`, expected: usize"#,
+//!     err.to_string()
+//! );
+//! ```
+//!
+pub mod parser;
+pub mod plan;
+pub mod value;
+
+pub use parser::{Expr, Literal, Op, ParseError, ParseErrorKind, Predicate, MAX_DEPTH};
+pub use plan::Plan;
+pub use value::{FromValue, Value};
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    marker::PhantomData,
+    ops::Bound,
+    str::FromStr,
+};
+
+use crate::{
+    collections::{
+        rw::{self, snapshot::Snapshot},
+        Retriever,
+    },
+    index::{
+        indices::Indices,
+        store::{Filterable, Indexer, Ordered},
+        Indexable,
+    },
+    query::plan::Node,
+};
+
+/// Error by parsing or executing a [`Query`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryError {
+    /// The query string is invalid.
+    Parse(ParseError),
+    /// There is no `Index` with the name `field` in the [`Schema`].
+    UnknownField { field: String, column: usize },
+    /// The `Value` can not convert into the `Key` type of the `Index`.
+    Type {
+        field: String,
+        column: usize,
+        expected: &'static str,
+        found: Value,
+    },
+    /// The operator is not supported by the `Index` (e.g. `<` for a not ordered `Index`).
+    UnsupportedOp {
+        field: String,
+        column: usize,
+        op: Op,
+    },
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::Parse(err) => err.fmt(f),
+            QueryError::UnknownField { field, column } => {
+                write!(f, "column {column}: unknown field `{field}`")
+            }
+            QueryError::Type {
+                field,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column {column}: invalid value {found} for field `{field}`, expected: {expected}"
+            ),
+            QueryError::UnsupportedOp { field, column, op } => {
+                write!(
+                    f,
+                    "column {column}: operator `{op}` is not supported by field `{field}`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl From<ParseError> for QueryError {
+    fn from(err: ParseError) -> Self {
+        QueryError::Parse(err)
+    }
+}
+
+/// A parsed query string, which can be executed against a [`Schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query(Expr);
+
+impl Query {
+    /// Parse the given query string.
+    pub fn parse(query: &str) -> Result<Self, ParseError> {
+        parser::parse(query).map(Self)
+    }
+
+    /// The parsed [`Expr`] of the query.
+    pub fn expr(&self) -> &Expr {
+        &self.0
+    }
+
+    /// Create the [`Plan`] for executing the query.
+    pub fn plan<'a, 's, T>(
+        &self,
+        schema: &'s Schema<'a, T>,
+    ) -> Result<Plan<'a, 's, T>, QueryError> {
+        Plan::new(schema, &self.0)
+    }
+
+    /// Describe the chosen [`Plan`]: the order of the predicates and the estimated number of positions.
+    pub fn explain<T>(&self, schema: &Schema<'_, T>) -> Result<String, QueryError> {
+        Ok(self.plan(schema)?.to_string())
+    }
+
+    /// Execute the query and return the `Indices` (positions) of all matching items.
+    pub fn execute<'a, T>(&self, schema: &Schema<'a, T>) -> Result<Indices<'a>, QueryError> {
+        Ok(self.plan(schema)?.execute())
+    }
+
+    /// Execute the query and return all matching items.
+    pub fn items<'a, T>(
+        &self,
+        schema: &Schema<'a, T>,
+    ) -> Result<impl Iterator<Item = &'a T>, QueryError> {
+        Ok(self.execute(schema)?.items(schema.items))
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// The `Items` of a [`Schema`], which are accessed by the `Indices` of the `Stores`.
+///
+/// ## Hint:
+/// For a [`crate::collections::rw::IList`] with the [`crate::collections::rw::RemoveMode::Tombstone`],
+/// the `Indices` are not the positions in the slice (`&l[..]`), so use the `IList` and not the slice.
+pub trait Source<T>: Indexable<usize, Output = T> {
+    /// Iterate over all `Items` with the `Index`, for a scan over the `Items`.
+    fn scan(&self) -> Box<dyn Iterator<Item = (usize, &T)> + '_>;
+
+    /// The number of `Items`.
+    fn count(&self) -> usize;
+}
+
+impl<T> Source<T> for &[T] {
+    fn scan(&self) -> Box<dyn Iterator<Item = (usize, &T)> + '_> {
+        Box::new(self.iter().enumerate())
+    }
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Source<T> for Vec<T> {
+    fn scan(&self) -> Box<dyn Iterator<Item = (usize, &T)> + '_> {
+        Box::new(self.iter().enumerate())
+    }
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<S, T, F> Source<T> for rw::IList<S, T, F>
+where
+    S: Indexer<T, F, Index = usize>,
+{
+    fn scan(&self) -> Box<dyn Iterator<Item = (usize, &T)> + '_> {
+        Box::new(self.iter_indexed())
+    }
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<S, T, F> Source<T> for Snapshot<S, T, F>
+where
+    S: Indexer<T, F, Index = usize>,
+{
+    fn scan(&self) -> Box<dyn Iterator<Item = (usize, &T)> + '_> {
+        Box::new(self.iter_indexed())
+    }
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+/// A `Schema` is a mapping from the `field` names of a query to the `Indices` of a collection.
+///
+/// All registered [`Retriever`] must be created for the same given `items`.
+pub struct Schema<'a, T> {
+    items: &'a dyn Source<T>,
+    fields: HashMap<&'a str, Box<dyn Field<'a, T> + 'a>>,
+}
+
+impl<'a, T> Schema<'a, T> {
+    /// Create a new `Schema` for the given `items` (see: [`Source`]),
+    /// e.g. a `Vec`, a `&[T]` or a [`crate::collections::rw::IList`].
+    pub fn new<C>(items: &'a C) -> Self
+    where
+        C: Source<T>,
+    {
+        Self {
+            items,
+            fields: HashMap::new(),
+        }
+    }
+
+    /// Register an `Index` with the given name, which supports the operators: `=` and `IN`.
+    pub fn field<F, I>(mut self, name: &'a str, retriever: Retriever<'a, F, I>) -> Self
+    where
+        F: Filterable<Index = usize>,
+        F::Key: FromValue,
+    {
+        self.fields
+            .insert(name, Box::new(EqField(retriever.0.filter)));
+        self
+    }
+
+    /// Register an ordered `Index` (e.g. [`crate::index::UniqueUIntIndex`]) with the given name,
+    /// which supports additional the operators: `<`, `<=`, `>`, `>=`.
+    pub fn ordered_field<F, I>(mut self, name: &'a str, retriever: Retriever<'a, F, I>) -> Self
+    where
+        F: Ordered<Index = usize>,
+        F::Key: FromValue,
+    {
+        self.fields
+            .insert(name, Box::new(OrderedField(retriever.0.filter)));
+        self
+    }
+
+    /// Register a field without an `Index`, which supports all operators.
+    /// The predicates for this field are executed by a scan over the `items`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fast_forward::{collections::ro::IRefList, index::MapIndex, query::Schema};
+    ///
+    /// let cars = vec![(1, "BMW"), (2, "VW"), (3, "BMW")];
+    /// let names = IRefList::<MapIndex, _>::new(|c| c.1.to_string(), &cars);
+    ///
+    /// let schema = Schema::new(&cars)
+    ///     .field("name", names.idx())
+    ///     .scan_field("id", |c| c.0);
+    ///
+    /// assert_eq!(
+    ///     vec![&(3, "BMW")],
+    ///     schema.query(r#"name = "BMW" AND id > 1"#).unwrap().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn scan_field<K, G>(mut self, name: &'a str, get: G) -> Self
+    where
+        G: Fn(&T) -> K + 'a,
+        K: FromValue + Ord + 'a,
+    {
+        self.fields.insert(
+            name,
+            Box::new(ScanField {
+                get,
+                _key: PhantomData,
+            }),
+        );
+        self
+    }
+
+    /// Parse and execute the given query string and return all matching items.
+    pub fn query(&self, query: &str) -> Result<impl Iterator<Item = &'a T>, QueryError> {
+        Ok(Query::parse(query)?.execute(self)?.items(self.items))
+    }
+
+    fn field_for(&self, p: &Predicate) -> Result<&(dyn Field<'a, T> + 'a), QueryError> {
+        self.fields
+            .get(p.field.as_str())
+            .map(|f| f.as_ref())
+            .ok_or_else(|| QueryError::UnknownField {
+                field: p.field.clone(),
+                column: p.column,
+            })
+    }
+}
+
+/// Create the [`Node`] of the [`Plan`] for a [`Predicate`].
+trait Field<'a, T> {
+    fn plan<'s>(&'s self, p: &Predicate) -> Result<Node<'a, 's, T>, QueryError>;
+}
+
+/// Convert the [`Literal`] into the `Key` of the `Index`.
+fn key<K: FromValue>(p: &Predicate, l: &Literal) -> Result<K, QueryError> {
+    K::from_value(&l.value).map_err(|expected| QueryError::Type {
+        field: p.field.clone(),
+        column: l.column,
+        expected,
+        found: l.value.clone(),
+    })
+}
+
+fn unsupported(p: &Predicate) -> QueryError {
+    QueryError::UnsupportedOp {
+        field: p.field.clone(),
+        column: p.column,
+        op: p.op,
+    }
+}
+
+/// `=` and `IN`
+fn eq<'a, F>(filter: &'a F, p: &Predicate) -> Option<Result<Vec<&'a [usize]>, QueryError>>
+where
+    F: Filterable<Index = usize>,
+    F::Key: FromValue,
+{
+    match p.op {
+        Op::Eq | Op::In => Some(
+            p.values
+                .iter()
+                .map(|l| key(p, l).map(|k| filter.get(&k)))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn index<'a, 's, T>(p: &Predicate, positions: Vec<&'a [usize]>) -> Node<'a, 's, T> {
+    Node::Index {
+        predicate: p.clone(),
+        positions,
+    }
+}
+
+struct EqField<'a, F>(&'a F);
+
+impl<'a, F, T> Field<'a, T> for EqField<'a, F>
+where
+    F: Filterable<Index = usize>,
+    F::Key: FromValue,
+{
+    fn plan<'s>(&'s self, p: &Predicate) -> Result<Node<'a, 's, T>, QueryError> {
+        let positions = eq(self.0, p).unwrap_or_else(|| Err(unsupported(p)))?;
+        Ok(index(p, positions))
+    }
+}
+
+struct OrderedField<'a, F>(&'a F);
+
+impl<'a, F, T> Field<'a, T> for OrderedField<'a, F>
+where
+    F: Ordered<Index = usize>,
+    F::Key: FromValue,
+{
+    fn plan<'s>(&'s self, p: &Predicate) -> Result<Node<'a, 's, T>, QueryError> {
+        if let Some(r) = eq(self.0, p) {
+            return Ok(index(p, r?));
+        }
+
+        let k: F::Key = key(p, &p.values[0])?;
+        let range = match p.op {
+            Op::Lt => (Bound::Unbounded, Bound::Excluded(k)),
+            Op::Le => (Bound::Unbounded, Bound::Included(k)),
+            Op::Gt => (Bound::Excluded(k), Bound::Unbounded),
+            _ => (Bound::Included(k), Bound::Unbounded),
+        };
+        Ok(index(p, self.0.range(range).collect()))
+    }
+}
+
+struct ScanField<G, K> {
+    get: G,
+    _key: PhantomData<K>,
+}
+
+impl<'a, G, K, T> Field<'a, T> for ScanField<G, K>
+where
+    G: Fn(&T) -> K,
+    K: FromValue + Ord,
+{
+    fn plan<'s>(&'s self, p: &Predicate) -> Result<Node<'a, 's, T>, QueryError> {
+        let mut keys = p
+            .values
+            .iter()
+            .map(|l| key(p, l))
+            .collect::<Result<Vec<K>, _>>()?;
+        keys.sort_unstable();
+
+        let op = p.op;
+        let get = &self.get;
+        let check = move |item: &T| {
+            let v = get(item);
+            match (op, keys.first()) {
+                (Op::In, _) => keys.binary_search(&v).is_ok(),
+                (_, None) => false,
+                (Op::Eq, Some(k)) => &v == k,
+                (Op::Lt, Some(k)) => &v < k,
+                (Op::Le, Some(k)) => &v <= k,
+                (Op::Gt, Some(k)) => &v > k,
+                (Op::Ge, Some(k)) => &v >= k,
+            }
+        };
+
+        Ok(Node::Scan {
+            predicate: p.clone(),
+            check: Box::new(check),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        collections::{
+            ro::IRefList,
+            rw::{IList, RemoveMode},
+        },
+        index::{MapIndex, MultiIntIndex, UniqueUIntIndex},
+    };
+    use rstest::{fixture, rstest};
+
+    #[derive(Debug, PartialEq)]
+    struct Car {
+        id: usize,
+        name: String,
+        price: i32,
+    }
+
+    impl Car {
+        fn new(id: usize, name: &str, price: i32) -> Self {
+            Self {
+                id,
+                name: name.into(),
+                price,
+            }
+        }
+    }
+
+    #[fixture]
+    fn cars() -> Vec<Car> {
+        vec![
+            Car::new(1, "BMW", 150),
+            Car::new(2, "VW", 80),
+            Car::new(3, "BMW", 90),
+            Car::new(4, "Audi", 120),
+            Car::new(5, "BMW", 200),
+        ]
+    }
+
+    fn ids<'a>(r: Result<impl Iterator<Item = &'a Car>, QueryError>) -> Vec<usize> {
+        r.unwrap().map(|c| c.id).collect()
+    }
+
+    #[rstest]
+    #[case::eq(r#"name = "BMW""#, vec![1, 3, 5])]
+    #[case::eq_not_found(r#"name = "Opel""#, vec![])]
+    #[case::in_list("id IN [5, 1, 99]", vec![1, 5])]
+    #[case::in_empty("id IN []", vec![])]
+    #[case::lt("price < 100", vec![2, 3])]
+    #[case::le("price <= 90", vec![2, 3])]
+    #[case::gt("price > 150", vec![5])]
+    #[case::ge("price >= 150", vec![1, 5])]
+    #[case::negative("price > -1000", vec![1, 2, 3, 4, 5])]
+    #[case::and(r#"name = "BMW" AND price < 160"#, vec![1, 3])]
+    #[case::or(r#"name = "VW" or id = 4"#, vec![2, 4])]
+    #[case::and_or(r#"name = "BMW" AND (id IN [1, 2, 3] OR price < 100)"#, vec![1, 3])]
+    #[case::and_before_or(r#"id = 2 OR name = "BMW" AND price > 100"#, vec![1, 2, 5])]
+    #[case::nested(r#"((id = 1) OR (id = 2)) AND (name = "VW")"#, vec![2])]
+    fn queries(cars: Vec<Car>, #[case] query: &str, #[case] expected: Vec<usize>) {
+        let id = IRefList::<UniqueUIntIndex, _>::new(|c| c.id, &cars);
+        let name = IRefList::<MapIndex, _>::new(|c| c.name.clone(), &cars);
+        let price = IRefList::<MultiIntIndex, _>::new(|c| c.price, &cars);
+
+        let schema = Schema::new(&cars)
+            .ordered_field("id", id.idx())
+            .field("name", name.idx())
+            .ordered_field("price", price.idx());
+
+        assert_eq!(expected, ids(schema.query(query)));
+        assert_eq!(expected, ids(Query::parse(query).unwrap().items(&schema)));
+    }
+
+    #[rstest]
+    fn long_chain(cars: Vec<Car>) {
+        let name = IRefList::<MapIndex, _>::new(|c| c.name.clone(), &cars);
+        let schema = Schema::new(&cars)
+            .field("name", name.idx())
+            .scan_field("price", |c| c.price);
+
+        let query = vec![r#"name = "BMW" AND price > 100"#; 100_000].join(" OR ");
+        assert_eq!(vec![1, 5], ids(schema.query(&query)));
+    }
+
+    #[rstest]
+    fn execute_indices(cars: Vec<Car>) {
+        let name = IRefList::<MapIndex, _>::new(|c| c.name.clone(), &cars);
+        let schema = Schema::new(&cars).field("name", name.idx());
+
+        let q: Query = r#"name = "VW" OR name = "Audi""#.parse().unwrap();
+        assert_eq!([1, 3], q.execute(&schema).unwrap());
+    }
+
+    #[rstest]
+    fn rw_list(cars: Vec<Car>) {
+        let mut cars = IList::<MapIndex, _, _>::from_vec(|c: &Car| c.name.clone(), cars);
+        cars.push(Car::new(6, "VW", 70));
+
+        let schema = Schema::new(&cars)
+            .field("name", cars.idx())
+            .scan_field("price", |c| c.price);
+        assert_eq!(vec![6], ids(schema.query(r#"name = "VW" AND price < 80"#)));
+    }
+
+    #[rstest]
+    #[case::index(r#"name = "BMW""#, vec![3, 5])]
+    #[case::index_last(r#"name = "Audi""#, vec![4])]
+    #[case::scan("price > 100", vec![4, 5])]
+    #[case::index_and_scan(r#"name = "BMW" AND price < 100"#, vec![3])]
+    fn rw_list_tombstones(cars: Vec<Car>, #[case] query: &str, #[case] expected: Vec<usize>) {
+        let mut cars = IList::<MapIndex, _, _>::from_vec(|c: &Car| c.name.clone(), cars)
+            .with_remove_mode(RemoveMode::Tombstone);
+        cars.remove(0);
+        cars.remove(0);
+
+        let schema = Schema::new(&cars)
+            .field("name", cars.idx())
+            .scan_field("price", |c| c.price);
+        assert_eq!(expected, ids(schema.query(query)));
+
+        let q = Query::parse(query).unwrap();
+        let found = q.execute(&schema).unwrap().items(&cars).map(|c| c.id);
+        assert_eq!(expected, found.collect::<Vec<_>>());
+    }
+
+    #[rstest]
+    #[case::parse("id = ", "column 6: expected value, found end of query")]
+    #[case::unknown_field("id = 1 AND color = 3", "column 12: unknown field `color`")]
+    #[case::type_str_for_int(
+        r#"id = "1""#,
+        r#"column 6: invalid value "1" for field `id`, expected: usize"#
+    )]
+    #[case::type_negative(
+        r#"id IN [1, -1]"#,
+        "column 11: invalid value -1 for field `id`, expected: usize"
+    )]
+    #[case::type_int_for_str(
+        "name = 1",
+        "column 8: invalid value 1 for field `name`, expected: string"
+    )]
+    #[case::type_overflow(
+        "price < 9999999999",
+        "column 9: invalid value 9999999999 for field `price`, expected: i32"
+    )]
+    #[case::unsupported(
+        r#"name > "BMW""#,
+        "column 1: operator `>` is not supported by field `name`"
+    )]
+    fn errors(cars: Vec<Car>, #[case] query: &str, #[case] msg: &str) {
+        let id = IRefList::<UniqueUIntIndex, _>::new(|c| c.id, &cars);
+        let name = IRefList::<MapIndex, _>::new(|c| c.name.clone(), &cars);
+        let price = IRefList::<MultiIntIndex, _>::new(|c| c.price, &cars);
+
+        let schema = Schema::new(&cars)
+            .ordered_field("id", id.idx())
+            .field("name", name.idx())
+            .ordered_field("price", price.idx());
+
+        assert_eq!(msg, schema.query(query).err().unwrap().to_string());
+    }
+}
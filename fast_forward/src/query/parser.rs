@@ -0,0 +1,592 @@
+//! Parser for the textual query language.
+//!
+//! ```text
+//! expr      := and ( OR and )*
+//! and       := primary ( AND primary )*
+//! primary   := '(' expr ')' | predicate
+//! predicate := field ( '=' | '<' | '<=' | '>' | '>=' ) value
+//!            | field IN '[' ( value ( ',' value )* )? ']'
+//! value     := integer | "string" | true | false
+//! ```
+//!
+//! The keywords `AND`, `OR` and `IN` are case-insensitive.
+//! All positions (`column`) are 1-based and counted in chars.
+use std::fmt::{self, Display};
+
+use crate::query::value::Value;
+
+/// The result of parsing a query string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Predicate(Predicate),
+    /// All terms of an `AND`-chain (at least two), nested `AND`s are flattened: `(a AND b) AND c` => `AND[a, b, c]`.
+    And(Vec<Expr>),
+    /// All terms of an `OR`-chain (at least two), nested `OR`s are flattened.
+    Or(Vec<Expr>),
+}
+
+/// A comparison of a `field` (the name of an `Index`) with one or many `values`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predicate {
+    pub field: String,
+    /// The position of the `field` in the query string.
+    pub column: usize,
+    pub op: Op,
+    pub values: Vec<Literal>,
+}
+
+impl Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.field, self.op)?;
+        match (self.op, self.values.as_slice()) {
+            (Op::In, _) | (_, []) => {
+                write!(f, "[")?;
+                for (i, l) in self.values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", l.value)?;
+                }
+                write!(f, "]")
+            }
+            (_, [l, ..]) => write!(f, "{}", l.value),
+        }
+    }
+}
+
+/// The comparison operator of a [`Predicate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Eq => write!(f, "="),
+            Op::Lt => write!(f, "<"),
+            Op::Le => write!(f, "<="),
+            Op::Gt => write!(f, ">"),
+            Op::Ge => write!(f, ">="),
+            Op::In => write!(f, "IN"),
+        }
+    }
+}
+
+/// A [`Value`] with the position in the query string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Literal {
+    pub value: Value,
+    pub column: usize,
+}
+
+/// Error by parsing a query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The position in the query string, where the error occurred.
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A char, which is not part of the query language.
+    InvalidChar(char),
+    /// A string without the closing `"`.
+    UnterminatedString,
+    /// A number, which is not a valid integer (e.g. to big).
+    InvalidNumber(String),
+    /// An other token was expected.
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+    /// The parentheses are nested deeper than [`MAX_DEPTH`].
+    TooDeep,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: ", self.column)?;
+        match &self.kind {
+            ParseErrorKind::InvalidChar(c) => write!(f, "invalid char: '{c}'"),
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            ParseErrorKind::InvalidNumber(n) => write!(f, "invalid number: {n}"),
+            ParseErrorKind::Unexpected { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            ParseErrorKind::TooDeep => {
+                write!(f, "parentheses are nested deeper than {MAX_DEPTH}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Value(Value),
+    And,
+    Or,
+    In,
+    Op(Op),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    End,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(i) => write!(f, "`{i}`"),
+            Token::Value(v) => write!(f, "`{v}`"),
+            Token::And => write!(f, "`AND`"),
+            Token::Or => write!(f, "`OR`"),
+            Token::In => write!(f, "`IN`"),
+            Token::Op(op) => write!(f, "`{op}`"),
+            Token::LParen => write!(f, "`(`"),
+            Token::RParen => write!(f, "`)`"),
+            Token::LBracket => write!(f, "`[`"),
+            Token::RBracket => write!(f, "`]`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::End => write!(f, "end of query"),
+        }
+    }
+}
+
+/// Split the query string into `Tokens` (with column).
+fn tokenize(query: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().enumerate().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        let column = pos + 1;
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '=' => Token::Op(Op::Eq),
+            '<' | '>' => {
+                let or_eq = chars.next_if(|(_, c)| *c == '=').is_some();
+                Token::Op(match (c, or_eq) {
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    _ => Op::Ge,
+                })
+            }
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => {
+                            if let Some((_, c)) = chars.next() {
+                                s.push(c)
+                            }
+                        }
+                        Some((_, c)) => s.push(c),
+                        None => {
+                            return Err(ParseError {
+                                column,
+                                kind: ParseErrorKind::UnterminatedString,
+                            })
+                        }
+                    }
+                }
+                Token::Value(Value::Str(s))
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                let mut n = String::from(c);
+                while let Some((_, d)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric()) {
+                    n.push(d);
+                }
+                match n.parse() {
+                    Ok(i) => Token::Value(Value::Int(i)),
+                    Err(_) => {
+                        return Err(ParseError {
+                            column,
+                            kind: ParseErrorKind::InvalidNumber(n),
+                        })
+                    }
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                }
+                match ident.to_ascii_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "IN" => Token::In,
+                    "TRUE" => Token::Value(Value::Bool(true)),
+                    "FALSE" => Token::Value(Value::Bool(false)),
+                    _ => Token::Ident(ident),
+                }
+            }
+            c => {
+                return Err(ParseError {
+                    column,
+                    kind: ParseErrorKind::InvalidChar(c),
+                })
+            }
+        };
+        tokens.push((token, column));
+    }
+
+    tokens.push((Token::End, query.chars().count() + 1));
+    Ok(tokens)
+}
+
+/// The maximum nesting of parentheses, which protects the recursive parser against a stack overflow.
+pub const MAX_DEPTH: usize = 64;
+
+/// Parse the given query string into an [`Expr`].
+pub fn parse(query: &str) -> Result<Expr, ParseError> {
+    let mut p = Parser {
+        tokens: tokenize(query)?,
+        pos: 0,
+        depth: 0,
+    };
+    let expr = p.expr()?;
+    p.expect(&Token::End, "`AND`, `OR` or end of query")?;
+    Ok(expr)
+}
+
+/// One term is the term itself, otherwise the `AND` or `OR` of all terms.
+fn chain(mut terms: Vec<Expr>, combine: fn(Vec<Expr>) -> Expr) -> Expr {
+    if terms.len() == 1 {
+        terms.remove(0)
+    } else {
+        combine(terms)
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &(Token, usize) {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> (Token, usize) {
+        let t = self.tokens[self.pos].clone();
+        if t.0 != Token::End {
+            self.pos += 1;
+        }
+        t
+    }
+
+    fn error<T>(&self, expected: &'static str) -> Result<T, ParseError> {
+        let (found, column) = self.peek();
+        Err(ParseError {
+            column: *column,
+            kind: ParseErrorKind::Unexpected {
+                expected,
+                found: found.to_string(),
+            },
+        })
+    }
+
+    fn expect(&mut self, token: &Token, expected: &'static str) -> Result<(), ParseError> {
+        if &self.peek().0 == token {
+            self.next();
+            Ok(())
+        } else {
+            self.error(expected)
+        }
+    }
+
+    // the chains are collected in a flat list (not a nested tree),
+    // so a long chain doesn't overflow the stack (by parsing, planning or dropping)
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut terms = Vec::new();
+        loop {
+            match self.and()? {
+                Expr::Or(nested) => terms.extend(nested),
+                e => terms.push(e),
+            }
+            if self.peek().0 != Token::Or {
+                return Ok(chain(terms, Expr::Or));
+            }
+            self.next();
+        }
+    }
+
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut terms = Vec::new();
+        loop {
+            match self.primary()? {
+                Expr::And(nested) => terms.extend(nested),
+                e => terms.push(e),
+            }
+            if self.peek().0 != Token::And {
+                return Ok(chain(terms, Expr::And));
+            }
+            self.next();
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        match self.peek().0 {
+            Token::LParen => {
+                if self.depth == MAX_DEPTH {
+                    return Err(ParseError {
+                        column: self.peek().1,
+                        kind: ParseErrorKind::TooDeep,
+                    });
+                }
+                self.next();
+                self.depth += 1;
+                let expr = self.expr()?;
+                self.depth -= 1;
+                self.expect(&Token::RParen, "`)`")?;
+                Ok(expr)
+            }
+            Token::Ident(_) => self.predicate(),
+            _ => self.error("field or `(`"),
+        }
+    }
+
+    fn predicate(&mut self) -> Result<Expr, ParseError> {
+        let (Token::Ident(field), column) = self.next() else {
+            unreachable!("predicate starts always with an Ident")
+        };
+
+        let (op, values) = match self.peek().0 {
+            Token::Op(op) => {
+                self.next();
+                (op, vec![self.literal()?])
+            }
+            Token::In => {
+                self.next();
+                self.expect(&Token::LBracket, "`[`")?;
+                let mut values = Vec::new();
+                if self.peek().0 != Token::RBracket {
+                    values.push(self.literal()?);
+                    while self.peek().0 == Token::Comma {
+                        self.next();
+                        values.push(self.literal()?);
+                    }
+                }
+                self.expect(&Token::RBracket, "`,` or `]`")?;
+                (Op::In, values)
+            }
+            _ => return self.error("operator (`=`, `<`, `<=`, `>`, `>=`, `IN`)"),
+        };
+
+        Ok(Expr::Predicate(Predicate {
+            field,
+            column,
+            op,
+            values,
+        }))
+    }
+
+    fn literal(&mut self) -> Result<Literal, ParseError> {
+        match self.peek() {
+            (Token::Value(_), _) => {
+                let (Token::Value(value), column) = self.next() else {
+                    unreachable!()
+                };
+                Ok(Literal { value, column })
+            }
+            _ => self.error("value"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn pred(field: &str, column: usize, op: Op, values: Vec<(Value, usize)>) -> Expr {
+        Expr::Predicate(Predicate {
+            field: field.into(),
+            column,
+            op,
+            values: values
+                .into_iter()
+                .map(|(value, column)| Literal { value, column })
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn simple_eq() {
+        assert_eq!(
+            pred("name", 1, Op::Eq, vec![(Value::Str("BMW".into()), 8)]),
+            parse(r#"name = "BMW""#).unwrap()
+        );
+        assert_eq!(
+            pred("id", 1, Op::Eq, vec![(Value::Int(-5), 4)]),
+            parse("id=-5").unwrap()
+        );
+        assert_eq!(
+            pred("active", 1, Op::Eq, vec![(Value::Bool(true), 10)]),
+            parse("active = TRUE").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::lt("<", Op::Lt)]
+    #[case::le("<=", Op::Le)]
+    #[case::gt(">", Op::Gt)]
+    #[case::ge(">=", Op::Ge)]
+    fn compare(#[case] op: &str, #[case] expected: Op) {
+        let Expr::Predicate(p) = parse(&format!("price {op} 100")).unwrap() else {
+            panic!("expected a predicate");
+        };
+        assert_eq!(expected, p.op);
+        assert_eq!(Value::Int(100), p.values[0].value);
+    }
+
+    #[test]
+    fn in_list() {
+        assert_eq!(
+            pred(
+                "id",
+                1,
+                Op::In,
+                vec![(Value::Int(1), 8), (Value::Int(2), 11), (Value::Int(3), 13)]
+            ),
+            parse("id in [1, 2,3]").unwrap()
+        );
+        assert_eq!(pred("id", 1, Op::In, vec![]), parse("id IN []").unwrap());
+    }
+
+    #[test]
+    fn escaped_string() {
+        assert_eq!(
+            pred(
+                "name",
+                1,
+                Op::Eq,
+                vec![(Value::Str(r#"a "b" \c"#.into()), 8)]
+            ),
+            parse(r#"name = "a \"b\" \\c""#).unwrap()
+        );
+    }
+
+    #[test]
+    fn and_binds_stronger_than_or() {
+        // a OR b AND c => a OR (b AND c)
+        assert_eq!(
+            Expr::Or(vec![
+                pred("a", 1, Op::Eq, vec![(Value::Int(1), 5)]),
+                Expr::And(vec![
+                    pred("b", 10, Op::Eq, vec![(Value::Int(2), 14)]),
+                    pred("c", 20, Op::Eq, vec![(Value::Int(3), 24)]),
+                ])
+            ]),
+            parse("a = 1 OR b = 2 AND c = 3").unwrap()
+        );
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(
+            Expr::And(vec![
+                pred("name", 1, Op::Eq, vec![(Value::Str("BMW".into()), 8)]),
+                Expr::Or(vec![
+                    pred(
+                        "id",
+                        19,
+                        Op::In,
+                        vec![(Value::Int(1), 26), (Value::Int(2), 28)]
+                    ),
+                    pred("price", 34, Op::Lt, vec![(Value::Int(100), 42)]),
+                ])
+            ]),
+            parse(r#"name = "BMW" AND (id IN [1,2] OR price < 100)"#).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::empty("", 1, "column 1: expected field or `(`, found end of query")]
+    #[case::missing_value("id = ", 6, "column 6: expected value, found end of query")]
+    #[case::missing_op(
+        "id 5",
+        4,
+        "column 4: expected operator (`=`, `<`, `<=`, `>`, `>=`, `IN`), found `5`"
+    )]
+    #[case::missing_paren("(id = 5", 8, "column 8: expected `)`, found end of query")]
+    #[case::missing_bracket(
+        "id IN [1, 2",
+        12,
+        "column 12: expected `,` or `]`, found end of query"
+    )]
+    #[case::trailing(
+        "id = 5 id",
+        8,
+        "column 8: expected `AND`, `OR` or end of query, found `id`"
+    )]
+    #[case::invalid_char("id = 5 & x = 3", 8, "column 8: invalid char: '&'")]
+    #[case::unterminated("name = \"BMW", 8, "column 8: unterminated string")]
+    #[case::invalid_number("id = 12a", 6, "column 6: invalid number: 12a")]
+    #[case::to_big(
+        "id = 99999999999999999999",
+        6,
+        "column 6: invalid number: 99999999999999999999"
+    )]
+    #[case::value_as_field("5 = id", 1, "column 1: expected field or `(`, found `5`")]
+    fn errors(#[case] query: &str, #[case] column: usize, #[case] msg: &str) {
+        let err = parse(query).unwrap_err();
+        assert_eq!(column, err.column);
+        assert_eq!(msg, err.to_string());
+    }
+
+    #[test]
+    fn flat_chain() {
+        // (a AND b) AND c => AND[a, b, c]
+        let a = |column| pred("a", column, Op::Eq, vec![(Value::Int(1), column + 4)]);
+        assert_eq!(
+            Expr::And(vec![a(2), a(12), a(23)]),
+            parse("(a = 1 AND a = 1) AND a = 1").unwrap()
+        );
+
+        let query = vec!["a = 1"; 200_000].join(" AND ");
+        match parse(&query).unwrap() {
+            Expr::And(terms) => assert_eq!(200_000, terms.len()),
+            e => panic!("expected AND, found: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        let query = |depth: usize| format!("{}id = 5{}", "(".repeat(depth), ")".repeat(depth));
+
+        assert_eq!(
+            pred(
+                "id",
+                MAX_DEPTH + 1,
+                Op::Eq,
+                vec![(Value::Int(5), MAX_DEPTH + 6)]
+            ),
+            parse(&query(MAX_DEPTH)).unwrap()
+        );
+
+        let err = parse(&query(100_000)).unwrap_err();
+        assert_eq!(MAX_DEPTH + 1, err.column);
+        assert_eq!(ParseErrorKind::TooDeep, err.kind);
+    }
+}
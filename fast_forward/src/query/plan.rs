@@ -0,0 +1,345 @@
+//! The query planner, which decides in which order the predicates of a query are executed.
+//!
+//! The planner use the statistics of the `Stores`:
+//! - positions per `Key`: the length of the `Indices` for a `Key` (`get(key).len()`)
+//! - total positions: every item has exactly one `Key` per `Store`,
+//!   so the total positions are the number of the items
+//!
+//! With this statistics, the planner estimate the number of positions for every predicate.
+//! `AND`-terms are executed with the smallest estimate first (most selective),
+//! all further terms are only checked against this (small) set of candidates.
+//! `OR`-terms are ordered by the estimate too, so the smallest sets are merged first.
+//!
+//! Predicates for fields without an `Index` fall back to a scan over the items.
+//! As part of an `AND` they are executed last and check only the candidates.
+use std::fmt::{self, Display};
+
+use crate::{
+    index::indices::Indices,
+    query::{Expr, Predicate, QueryError, Schema, Source},
+};
+
+/// The plan for one part of a query.
+pub(crate) enum Node<'a, 's, T> {
+    /// Lookup the `Indices` in an `Index`, the positions are grouped by `Key`.
+    Index {
+        predicate: Predicate,
+        positions: Vec<&'a [usize]>,
+    },
+    /// Check every item with the given function.
+    Scan {
+        predicate: Predicate,
+        check: Box<dyn Fn(&T) -> bool + 's>,
+    },
+    And(Vec<Node<'a, 's, T>>),
+    Or(Vec<Node<'a, 's, T>>),
+}
+
+impl<'a, 's, T> Node<'a, 's, T> {
+    /// The estimated number of positions, which this part returns.
+    fn estimate(&self, total: usize) -> usize {
+        match self {
+            Node::Index { positions, .. } => positions.iter().map(|p| p.len()).sum(),
+            Node::Scan { .. } => total,
+            Node::And(nodes) => nodes.iter().map(|n| n.estimate(total)).min().unwrap_or(0),
+            Node::Or(nodes) => nodes
+                .iter()
+                .map(|n| n.estimate(total))
+                .sum::<usize>()
+                .min(total),
+        }
+    }
+
+    /// Is it cheaper to check every candidate (binary search), than to merge all positions.
+    fn probe(&self, candidates: usize, total: usize) -> bool {
+        match self {
+            Node::Index { positions, .. } => candidates * positions.len() < self.estimate(total),
+            _ => true,
+        }
+    }
+
+    /// Sort the terms of `AND` and `OR` by the estimate (ascending).
+    /// For equal estimates, are Index-Lookups preferred to Scans.
+    fn optimize(self, total: usize) -> Self {
+        let sort = |nodes: Vec<Self>| {
+            let mut nodes = nodes
+                .into_iter()
+                .map(|n| n.optimize(total))
+                .collect::<Vec<_>>();
+            nodes.sort_by_key(|n| (n.estimate(total), matches!(n, Node::Scan { .. })));
+            nodes
+        };
+
+        match self {
+            Node::And(nodes) => Node::And(sort(nodes)),
+            Node::Or(nodes) => Node::Or(sort(nodes)),
+            n => n,
+        }
+    }
+
+    /// Execute this part and return all matching positions.
+    fn execute(&self, items: &'a dyn Source<T>) -> Indices<'a> {
+        match self {
+            Node::Index { positions, .. } => match positions.as_slice() {
+                [] => Indices::empty(),
+                [p] => Indices::from_sorted_slice(p),
+                ps => ps.concat().into(),
+            },
+            Node::Scan { check, .. } => items
+                .scan()
+                .filter_map(|(pos, item)| check(item).then_some(pos))
+                .collect::<Vec<_>>()
+                .into(),
+            Node::And(nodes) => match nodes.split_first() {
+                Some((first, rest)) => rest
+                    .iter()
+                    .fold(first.execute(items), |c, n| n.filter(c, items)),
+                None => Indices::empty(),
+            },
+            Node::Or(nodes) => nodes
+                .iter()
+                .fold(Indices::empty(), |r, n| r | n.execute(items)),
+        }
+    }
+
+    /// Return all given `candidates`, which are matching this part.
+    fn filter(&self, candidates: Indices<'a>, items: &'a dyn Source<T>) -> Indices<'a> {
+        if candidates.as_slice().is_empty() {
+            return candidates;
+        }
+
+        match self {
+            Node::Index { positions, .. }
+                if self.probe(candidates.as_slice().len(), items.count()) =>
+            {
+                candidates
+                    .as_slice()
+                    .iter()
+                    .filter(|pos| positions.iter().any(|p| p.binary_search(pos).is_ok()))
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .into()
+            }
+            Node::Index { .. } => candidates & self.execute(items),
+            Node::Scan { check, .. } => candidates
+                .as_slice()
+                .iter()
+                .filter(|pos| check(items.item(pos)))
+                .copied()
+                .collect::<Vec<_>>()
+                .into(),
+            Node::And(nodes) => nodes.iter().fold(candidates, |c, n| n.filter(c, items)),
+            Node::Or(nodes) => nodes.iter().fold(Indices::empty(), |r, n| {
+                r | n.filter(candidates.clone(), items)
+            }),
+        }
+    }
+
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        total: usize,
+        level: usize,
+        how: &str,
+    ) -> fmt::Result {
+        let estimate = self.estimate(total);
+        write!(f, "{:indent$}", "", indent = level * 2)?;
+        match self {
+            Node::Index { predicate, .. } => write!(f, "INDEX {predicate}")?,
+            Node::Scan { predicate, .. } => write!(f, "SCAN {predicate}")?,
+            Node::And(_) => write!(f, "AND")?,
+            Node::Or(_) => write!(f, "OR")?,
+        };
+        writeln!(f, " (estimate: {estimate}{how})")?;
+
+        match self {
+            Node::And(nodes) => {
+                let mut candidates = total;
+                for (i, n) in nodes.iter().enumerate() {
+                    let how = match n {
+                        _ if i == 0 => "",
+                        Node::Scan { .. } => ", check candidates",
+                        _ if n.probe(candidates, total) => ", probe candidates",
+                        _ => ", intersect",
+                    };
+                    n.fmt(f, total, level + 1, how)?;
+                    candidates = candidates.min(n.estimate(total));
+                }
+            }
+            Node::Or(nodes) => {
+                for n in nodes {
+                    n.fmt(f, total, level + 1, "")?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// The chosen plan for executing a [`crate::query::Query`].
+///
+/// The `Display` implementation describes the plan (`explain`), for example:
+///
+/// ```text
+/// AND (estimate: 2)
+///   INDEX name = "BMW" (estimate: 3)
+///   OR (estimate: 5, probe candidates)
+///     INDEX price < 100 (estimate: 2)
+///     INDEX id IN [1, 2, 3] (estimate: 3)
+/// ```
+pub struct Plan<'a, 's, T> {
+    root: Node<'a, 's, T>,
+    items: &'a dyn Source<T>,
+}
+
+impl<'a, 's, T> Plan<'a, 's, T> {
+    /// Create a plan for the given [`Expr`].
+    pub(crate) fn new(schema: &'s Schema<'a, T>, expr: &Expr) -> Result<Self, QueryError> {
+        let total = schema.items.count();
+        Ok(Self {
+            root: Self::node(schema, expr)?.optimize(total),
+            items: schema.items,
+        })
+    }
+
+    fn node(schema: &'s Schema<'a, T>, expr: &Expr) -> Result<Node<'a, 's, T>, QueryError> {
+        let terms = |terms: &[Expr]| {
+            terms
+                .iter()
+                .map(|e| Self::node(schema, e))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        match expr {
+            Expr::And(es) => Ok(Node::And(terms(es)?)),
+            Expr::Or(es) => Ok(Node::Or(terms(es)?)),
+            Expr::Predicate(p) => schema.field_for(p)?.plan(p),
+        }
+    }
+
+    /// The estimated number of matching positions.
+    pub fn estimate(&self) -> usize {
+        self.root.estimate(self.items.count())
+    }
+
+    /// Execute the plan and return the `Indices` (positions) of all matching items.
+    pub fn execute(&self) -> Indices<'a> {
+        self.root.execute(self.items)
+    }
+}
+
+impl<T> Display for Plan<'_, '_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.root.fmt(f, self.items.count(), 0, "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collections::ro::IRefList,
+        index::{MapIndex, MultiIntIndex, UniqueUIntIndex},
+        query::{Query, Schema},
+    };
+    use rstest::{fixture, rstest};
+
+    struct Car {
+        id: usize,
+        name: &'static str,
+        price: i32,
+    }
+
+    #[fixture]
+    fn cars() -> Vec<Car> {
+        [
+            (1, "BMW", 150),
+            (2, "VW", 80),
+            (3, "BMW", 90),
+            (4, "Audi", 120),
+            (5, "BMW", 200),
+        ]
+        .into_iter()
+        .map(|(id, name, price)| Car { id, name, price })
+        .collect()
+    }
+
+    #[rstest]
+    fn most_selective_first(cars: Vec<Car>) {
+        let id = IRefList::<UniqueUIntIndex, _>::new(|c| c.id, &cars);
+        let name = IRefList::<MapIndex, _>::new(|c| c.name.to_string(), &cars);
+        let price = IRefList::<MultiIntIndex, _>::new(|c| c.price, &cars);
+
+        let schema = Schema::new(&cars)
+            .ordered_field("id", id.idx())
+            .field("name", name.idx())
+            .ordered_field("price", price.idx());
+
+        let q = Query::parse(r#"name = "BMW" AND price > 100 AND id = 5"#).unwrap();
+        assert_eq!(
+            r#"AND (estimate: 1)
+  INDEX id = 5 (estimate: 1)
+  INDEX name = "BMW" (estimate: 3, probe candidates)
+  INDEX price > 100 (estimate: 3, intersect)
+"#,
+            q.explain(&schema).unwrap()
+        );
+        assert_eq!([4], q.execute(&schema).unwrap());
+
+        let q = Query::parse(r#"name = "BMW" OR id IN [2, 4]"#).unwrap();
+        assert_eq!(
+            r#"OR (estimate: 5)
+  INDEX id IN [2, 4] (estimate: 2)
+  INDEX name = "BMW" (estimate: 3)
+"#,
+            q.explain(&schema).unwrap()
+        );
+        assert_eq!(5, q.plan(&schema).unwrap().estimate());
+        assert_eq!([0, 1, 2, 3, 4], q.execute(&schema).unwrap());
+    }
+
+    #[rstest]
+    fn scan_last(cars: Vec<Car>) {
+        let name = IRefList::<MapIndex, _>::new(|c| c.name.to_string(), &cars);
+
+        let schema = Schema::new(&cars)
+            .scan_field("price", |c| c.price)
+            .field("name", name.idx());
+
+        let q = Query::parse(r#"price >= 150 AND (name = "BMW" OR name = "VW")"#).unwrap();
+        assert_eq!(
+            r#"AND (estimate: 4)
+  OR (estimate: 4)
+    INDEX name = "VW" (estimate: 1)
+    INDEX name = "BMW" (estimate: 3)
+  SCAN price >= 150 (estimate: 5, check candidates)
+"#,
+            q.explain(&schema).unwrap()
+        );
+        assert_eq!([0, 4], q.execute(&schema).unwrap());
+    }
+
+    #[rstest]
+    #[case::eq("price = 90", vec![3])]
+    #[case::in_list("price IN [200, 80, 1]", vec![2, 5])]
+    #[case::lt("price < 100", vec![2, 3])]
+    #[case::le("price <= 90", vec![2, 3])]
+    #[case::gt("price > 150", vec![5])]
+    #[case::ge("price >= 150", vec![1, 5])]
+    #[case::and_index(r#"price < 100 AND name = "BMW""#, vec![3])]
+    #[case::or_index(r#"price < 100 OR name = "Audi""#, vec![2, 3, 4])]
+    fn scan(cars: Vec<Car>, #[case] query: &str, #[case] expected: Vec<usize>) {
+        let name = IRefList::<MapIndex, _>::new(|c| c.name.to_string(), &cars);
+
+        let schema = Schema::new(&cars)
+            .scan_field("price", |c| c.price)
+            .field("name", name.idx());
+
+        let ids = schema
+            .query(query)
+            .unwrap()
+            .map(|c| c.id)
+            .collect::<Vec<_>>();
+        assert_eq!(expected, ids);
+    }
+}
@@ -0,0 +1,137 @@
+//! The `Values` of a query and the conversion into the `Key` of an `Index`.
+use std::fmt::{self, Display};
+
+/// A `Value` from a query string.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Value {
+    Int(i64),
+    Str(String),
+    Bool(bool),
+}
+
+impl Value {
+    /// The name of the type, e.g. for error messages.
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "integer",
+            Value::Str(_) => "string",
+            Value::Bool(_) => "bool",
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{i}"),
+            Value::Str(s) => write!(f, "{s:?}"),
+            Value::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+/// Convert a [`Value`] into the `Key` of an `Index`.
+/// Returns the name of the expected type, if the conversion is not possible.
+///
+/// # Example
+///
+/// ```
+/// use fast_forward::query::{FromValue, Value};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Gender { Male, Female }
+///
+/// impl FromValue for Gender {
+///     fn from_value(value: &Value) -> Result<Self, &'static str> {
+///         match value {
+///             Value::Str(s) if s == "male" => Ok(Gender::Male),
+///             Value::Str(s) if s == "female" => Ok(Gender::Female),
+///             _ => Err("gender (\"male\" or \"female\")"),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Ok(Gender::Male), Gender::from_value(&Value::Str("male".into())));
+/// assert!(Gender::from_value(&Value::Int(1)).is_err());
+/// ```
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self, &'static str>;
+}
+
+macro_rules! int_from_value {
+    ( $( $t:ty ),* ) => {
+        $(
+        impl FromValue for $t {
+            fn from_value(value: &Value) -> Result<Self, &'static str> {
+                match value {
+                    Value::Int(i) => (*i).try_into().map_err(|_| stringify!($t)),
+                    _ => Err(stringify!($t)),
+                }
+            }
+        }
+        )*
+    };
+}
+
+int_from_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self, &'static str> {
+        match value {
+            Value::Str(s) => Ok(s.clone()),
+            _ => Err("string"),
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self, &'static str> {
+        match value {
+            Value::Bool(b) => Ok(*b),
+            _ => Err("bool"),
+        }
+    }
+}
+
+impl FromValue for char {
+    fn from_value(value: &Value) -> Result<Self, &'static str> {
+        match value {
+            Value::Str(s) if s.chars().count() == 1 => Ok(s.chars().next().unwrap()),
+            _ => Err("char"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ints() {
+        assert_eq!(Ok(5u8), u8::from_value(&Value::Int(5)));
+        assert_eq!(Ok(-5i32), i32::from_value(&Value::Int(-5)));
+        assert_eq!(Err("u8"), u8::from_value(&Value::Int(256)));
+        assert_eq!(Err("usize"), usize::from_value(&Value::Int(-1)));
+        assert_eq!(Err("i64"), i64::from_value(&Value::Str("1".into())));
+    }
+
+    #[test]
+    fn others() {
+        assert_eq!(
+            Ok(String::from("a")),
+            String::from_value(&Value::Str("a".into()))
+        );
+        assert_eq!(Err("string"), String::from_value(&Value::Int(1)));
+        assert_eq!(Ok(true), bool::from_value(&Value::Bool(true)));
+        assert_eq!(Err("bool"), bool::from_value(&Value::Str("true".into())));
+        assert_eq!(Ok('x'), char::from_value(&Value::Str("x".into())));
+        assert_eq!(Err("char"), char::from_value(&Value::Str("xy".into())));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("-3", Value::Int(-3).to_string());
+        assert_eq!(r#""BMW""#, Value::Str("BMW".into()).to_string());
+        assert_eq!("false", Value::Bool(false).to_string());
+    }
+}
@@ -0,0 +1,211 @@
+#![cfg(feature = "serde")]
+
+use fast_forward::{
+    collections::{ro, rw, rw::RemoveMode},
+    index::{
+        indices::{Indices, KeyIndex, MultiKeyIndex, UniqueKeyIndex},
+        MapIndex, MultiIntIndex, MultiUIntIndex, SortedIndex, UniqueIntIndex, UniqueUIntIndex,
+    },
+};
+use rstest::{fixture, rstest};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+struct Car(usize, String);
+
+#[fixture]
+fn cars() -> Vec<Car> {
+    vec![
+        Car(2, "BMW".into()),
+        Car(5, "Audi".into()),
+        Car(2, "VW".into()),
+        Car(99, "Porsche".into()),
+    ]
+}
+
+#[test]
+fn key_indices() {
+    let mut i = MultiKeyIndex::new(7);
+    i.add(1);
+    i.add(3);
+    let json = serde_json::to_string(&i).unwrap();
+    assert_eq!("[1,3,7]", json);
+    let i: MultiKeyIndex = serde_json::from_str(&json).unwrap();
+    assert_eq!(&[1, 3, 7], i.as_slice());
+
+    let json = serde_json::to_string(&UniqueKeyIndex::new(4)).unwrap();
+    assert_eq!("[4]", json);
+    let i: UniqueKeyIndex = serde_json::from_str(&json).unwrap();
+    assert_eq!(&[4], i.as_slice());
+
+    let json = serde_json::to_string(&Indices::from([5, 2])).unwrap();
+    assert_eq!("[2,5]", json);
+    assert_eq!([2, 5], serde_json::from_str::<Indices>(&json).unwrap());
+}
+
+#[rstest]
+#[case::not_sorted("[3,1]")]
+#[case::duplicate("[1,1]")]
+#[case::empty("[]")]
+fn invalid_multi_key_index(#[case] json: &str) {
+    assert!(serde_json::from_str::<MultiKeyIndex>(json).is_err());
+}
+
+#[rstest]
+#[case::empty("[]")]
+#[case::two("[1,2]")]
+fn invalid_unique_key_index(#[case] json: &str) {
+    assert!(serde_json::from_str::<UniqueKeyIndex>(json).is_err());
+}
+
+#[test]
+fn invalid_indices() {
+    assert!(serde_json::from_str::<Indices>("[2,1]").is_err());
+}
+
+#[rstest]
+fn ro_list(cars: Vec<Car>) {
+    type Cars = ro::IList<(MultiUIntIndex, MultiIntIndex, MapIndex, SortedIndex), Car>;
+    let l = Cars::new_multi(
+        (
+            |c: &Car| c.0,
+            |c: &Car| c.0 as i32 - 5,
+            |c: &Car| c.1.clone(),
+            |c: &Car| c.1.clone(),
+        ),
+        cars.clone(),
+    );
+
+    let json = serde_json::to_string(&l).unwrap();
+    let l: Cars = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(&cars, &l[..]);
+    assert_eq!(2, l.idx_by(|(ids, _, _, _)| ids).get(&2).count());
+    assert_eq!(
+        Some(&Car(2, "BMW".into())),
+        l.idx_by(|(_, ints, _, _)| ints).get(&-3).next()
+    );
+    assert!(l.idx_by(|(_, _, names, _)| names).contains(&"VW".into()));
+    assert_eq!(
+        vec![&Car(5, "Audi".into()), &Car(2, "BMW".into())],
+        l.idx_by(|(_, _, _, sorted)| sorted)
+            .get_many(["Audi".into(), "BMW".into()])
+            .collect::<Vec<_>>()
+    );
+}
+
+#[rstest]
+#[case::map(r#"{"store":{"Audi":[1],"BMW":[3]},"items":[[5,"Audi"],[2,"BMW"]]}"#)]
+#[case::not_sorted(r#"{"store":{"Audi":[1,0]},"items":[[5,"Audi"],[2,"BMW"]]}"#)]
+fn invalid_ro_list(#[case] json: &str) {
+    assert!(serde_json::from_str::<ro::IList<MapIndex, Car>>(json).is_err());
+}
+
+#[test]
+fn invalid_ro_list_uint() {
+    let json = r#"{"store":[null,null,[5]],"items":[[2,"BMW"]]}"#;
+    let err = serde_json::from_str::<ro::IList<UniqueUIntIndex, Car>>(json)
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("out of bounds"));
+}
+
+#[rstest]
+fn rw_list(#[values(RemoveMode::Swap, RemoveMode::Tombstone)] mode: RemoveMode) {
+    let fields = (|c: &Car| c.0 as i32, |c: &Car| c.1.clone());
+    let mut l = rw::IList::<(UniqueIntIndex, MapIndex), _, _>::from_vec(
+        fields,
+        vec![
+            Car(0, "Opel".into()),
+            Car(2, "BMW".into()),
+            Car(5, "Audi".into()),
+        ],
+    )
+    .with_remove_mode(mode);
+    l.remove(0);
+
+    let json = serde_json::to_string(&l).unwrap();
+    let mut l2 = rw::IList::<(UniqueIntIndex, MapIndex), Car, _>::deserialize_with(
+        fields,
+        &mut serde_json::Deserializer::from_str(&json),
+    )
+    .unwrap();
+
+    assert_eq!(&l[..], &l2[..]);
+    assert!(!l2.idx_by(|(ids, _)| ids).contains(&0));
+    assert_eq!(
+        Some(&Car(5, "Audi".into())),
+        l2.idx_by(|(_, names)| names).get(&"Audi".into()).next()
+    );
+
+    // the deserialized List can be changed like the original List
+    for l in [&mut l, &mut l2] {
+        l.push(Car(0, "Opel".into()));
+        l.remove(0);
+    }
+    assert_eq!(&l[..], &l2[..]);
+    assert_eq!(
+        Some(&Car(0, "Opel".into())),
+        l2.idx_by(|(ids, _)| ids).get(&0).next()
+    );
+}
+
+#[rstest]
+#[case::not_in_store(
+    r#"{"store":[[0]],"items":[[1,"BMW"]],"tombstones":[],"mode":"Swap"}"#,
+    "not in the store"
+)]
+#[case::tombstone_in_store(
+    r#"{"store":[[0],[1]],"items":[[0,"BMW"]],"tombstones":[1],"mode":"Tombstone"}"#,
+    "number of positions"
+)]
+#[case::position_twice(
+    r#"{"store":[[0],[0]],"items":[[0,"BMW"],[1,"VW"]],"tombstones":[],"mode":"Swap"}"#,
+    "position 1 is not in the store"
+)]
+#[case::store_out_of_bounds(
+    r#"{"store":[[0],null,null,[3]],"items":[[0,"BMW"]],"tombstones":[],"mode":"Swap"}"#,
+    "out of bounds"
+)]
+#[case::tombstones_not_sorted(
+    r#"{"store":[],"items":[],"tombstones":[1,0],"mode":"Tombstone"}"#,
+    "tombstones"
+)]
+#[case::tombstone_out_of_bounds(
+    r#"{"store":[],"items":[],"tombstones":[1],"mode":"Tombstone"}"#,
+    "tombstones"
+)]
+#[case::tombstones_without_tombstone_mode(
+    r#"{"store":[[1]],"items":[[0,"BMW"]],"tombstones":[0],"mode":"Swap"}"#,
+    "RemoveMode"
+)]
+fn invalid_rw_list(#[case] json: &str, #[case] err: &str) {
+    let r = rw::IList::<UniqueUIntIndex, Car, _>::deserialize_with(
+        |c: &Car| c.0,
+        &mut serde_json::Deserializer::from_str(json),
+    );
+    assert!(r.err().unwrap().to_string().contains(err));
+}
+
+#[test]
+fn invalid_rw_list_secondary_store() {
+    let fields = (|c: &Car| c.0, |c: &Car| c.1.clone());
+    let l = rw::IList::<(UniqueUIntIndex, MapIndex), _, _>::from_vec(
+        fields,
+        vec![Car(0, "BMW".into()), Car(1, "VW".into())],
+    );
+    let json = serde_json::to_string(&l)
+        .unwrap()
+        .replace(r#""VW":[1]"#, r#""Audi":[1]"#);
+    assert!(json.contains("Audi"));
+
+    let r = rw::IList::<(UniqueUIntIndex, MapIndex), Car, _>::deserialize_with(
+        fields,
+        &mut serde_json::Deserializer::from_str(&json),
+    );
+    assert!(r
+        .err()
+        .unwrap()
+        .to_string()
+        .contains("position 1 is not in the store"));
+}